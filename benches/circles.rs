@@ -0,0 +1,54 @@
+//! Times drawing circles on a headless canvas.
+//!
+//! Run with `cargo bench --bench circles`.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use clod::{
+    style::{Circle, CircleLike, Gradient, GradientLike},
+    State,
+};
+use glam::{U16Vec2, Vec2};
+use rgb::Rgb;
+
+const ITERATIONS: u32 = 2_000;
+
+/// Draws `count` circles spread over the canvas and returns the average time
+/// per circle.
+fn time_circles(state: &mut State, count: u32, circle: &Circle) -> Duration {
+    let size = state.canvas_size().as_vec2();
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        for i in 0..count {
+            let t = i as f32 / count as f32;
+            let pos = Vec2::new(t * size.x, (t * 7.0).fract() * size.y);
+            state.aa_circle(black_box(pos), black_box(circle.clone()));
+        }
+    }
+    start.elapsed() / (ITERATIONS * count)
+}
+
+fn main() {
+    let mut state = State::headless(U16Vec2::new(200, 60));
+    let gradient = Gradient::linear(Vec2::ZERO, Vec2::new(200.0, 0.0))
+        .stop(0.0, Rgb::new(255, 0, 0))
+        .stop(1.0, Rgb::new(0, 0, 255));
+
+    let cases = [
+        ("small solid", Circle::with_radius(2.0).solid()),
+        ("large solid", Circle::with_radius(20.0).solid()),
+        ("thin ring", Circle::with_radius(10.0).stroke(1.0)),
+        ("thick ring", Circle::with_radius(15.0).stroke(6.0)),
+        (
+            "gradient ring",
+            Circle::with_radius(15.0).stroke(6.0).stroke_color(gradient),
+        ),
+    ];
+    for (name, circle) in cases {
+        let per_circle = time_circles(&mut state, 100, &circle);
+        println!("{name:<14} {per_circle:>10.2?} per circle");
+    }
+}
@@ -1,5 +1,6 @@
 use clod::{
-    style::{CanvasAlignment, CircleLike, Stylize},
+    camera::Camera,
+    style::{CanvasAlignment, CircleLike, LineCap, Stroke, StrokeLike, Stylize},
     App, AppResult,
 };
 use crossterm::style::Color;
@@ -28,52 +29,58 @@ struct MyApp {
 
 impl App for MyApp {
     fn update(&mut self, state: &mut clod::State) -> Result<(), String> {
-        let bounds = state.canvas_size().as_vec2() - Vec2::ONE;
-        self.main_body.body.vel += self.main_body.body.acc * self.zoom;
-        self.main_body.body.vel += Vec2::new(0.0, 9.8) * state.delta_seconds() * self.zoom;
-        self.main_body.body.pos +=
-            self.main_body.body.vel * state.delta_seconds() * self.zoom;
+        // simulate in world units and let the camera scale them onto the canvas
+        let bounds = (state.canvas_size().as_vec2() - Vec2::ONE) / self.zoom;
+        state.set_camera(Some(Camera {
+            zoom: self.zoom,
+            ..Camera::looking_at(bounds / 2.0)
+        }));
+
+        self.main_body.body.vel += self.main_body.body.acc;
+        self.main_body.body.vel += Vec2::new(0.0, 9.8) * state.delta_seconds();
+        self.main_body.body.pos += self.main_body.body.vel * state.delta_seconds();
         self.main_body.body.vel.y *= 0.99;
         self.main_body.body.vel.x *= 0.99;
         // self.main_body.acc *= 0.0;
 
-        if self.main_body.body.vel.x.abs() < 0.2 {
+        if self.main_body.body.vel.x.abs() < 0.04 {
             self.main_body.body.vel.x = 0.0;
         }
 
-        if (self.main_body.body.pos.y + self.main_body.radius * self.zoom) >= bounds.y {
+        if (self.main_body.body.pos.y + self.main_body.radius) >= bounds.y {
             self.main_body.body.vel.y *= -1.0;
-            self.main_body.body.pos.y = bounds.y - self.main_body.radius * self.zoom;
+            self.main_body.body.pos.y = bounds.y - self.main_body.radius;
             self.main_body.body.vel.x *= 0.98;
         }
 
-        if (self.main_body.body.pos.x + self.main_body.radius * self.zoom) >= bounds.x {
+        if (self.main_body.body.pos.x + self.main_body.radius) >= bounds.x {
             self.main_body.body.vel.x *= -1.0;
-            self.main_body.body.pos.x = bounds.x - self.main_body.radius * self.zoom;
+            self.main_body.body.pos.x = bounds.x - self.main_body.radius;
         }
 
-        if (self.main_body.body.pos.x - self.main_body.radius * self.zoom) < 0.0 {
+        if (self.main_body.body.pos.x - self.main_body.radius) < 0.0 {
             self.main_body.body.vel.x *= -1.0;
-            self.main_body.body.pos.x = self.main_body.radius * self.zoom;
+            self.main_body.body.pos.x = self.main_body.radius;
         }
 
-        state.aa_line(self.main_body.body.pos, Vec2::ZERO);
-        state.aa_line(self.main_body.body.pos, bounds);
-        state.aa_line(bounds - self.main_body.body.pos, bounds);
-        state.aa_line_with_color(
+        let line = Stroke::with_width(0.4).cap(LineCap::Round);
+        state.stroke_line(self.main_body.body.pos, Vec2::ZERO, line.clone());
+        state.stroke_line(self.main_body.body.pos, bounds, line.clone());
+        state.stroke_line(bounds - self.main_body.body.pos, bounds, line.clone());
+        state.stroke_line(
             Vec2::ZERO,
             bounds - self.main_body.body.pos,
-            Rgb {
+            line.color(Rgb {
                 r: 200,
                 g: 100,
                 b: 200,
-            },
+            }),
         );
 
         state.aa_circle(
             self.main_body.body.pos,
-            clod::style::Circle::with_radius(self.main_body.radius * self.zoom)
-                .stroke(3.0)
+            clod::style::Circle::with_radius(self.main_body.radius)
+                .stroke(0.6)
                 .stroke_color(Rgb::new(180, 123, 43)),
         );
 
@@ -104,16 +111,16 @@ impl App for MyApp {
                 self.zoom += 0.1;
             }
             crossterm::event::KeyCode::Char('a') | crossterm::event::KeyCode::Left => {
-                self.main_body.body.acc = Vec2::new(-2.0, 0.0) * self.zoom;
+                self.main_body.body.acc = Vec2::new(-2.0, 0.0);
             }
             crossterm::event::KeyCode::Char('s') | crossterm::event::KeyCode::Down => {
-                self.zoom -= 0.1;
+                self.zoom = (self.zoom - 0.1).max(0.5);
             }
             crossterm::event::KeyCode::Char('d') | crossterm::event::KeyCode::Right => {
-                self.main_body.body.acc = Vec2::new(2.0, 0.0) * self.zoom;
+                self.main_body.body.acc = Vec2::new(2.0, 0.0);
             }
             crossterm::event::KeyCode::Char(' ') => {
-                self.main_body.body.acc = Vec2::new(0.0, -2.0) * self.zoom;
+                self.main_body.body.acc = Vec2::new(0.0, -2.0);
                 self.counter += 1;
             }
             _ => {}
@@ -121,9 +128,9 @@ impl App for MyApp {
     }
 
     fn init(&mut self, state: &mut clod::State) -> Result<(), String> {
-        self.main_body.body.pos = state.canvas_size().as_vec2() / 2.0;
         self.main_body.radius = 1.0;
         self.zoom = 5.0;
+        self.main_body.body.pos = state.canvas_size().as_vec2() / self.zoom / 2.0;
         state.set_background_color(Some(Color::Rgb { r: 0, g: 0, b: 0 }));
         Ok(())
     }
@@ -16,6 +16,7 @@ use rapier2d::{
 
 struct Entity {
     handle: RigidBodyHandle,
+    previous: Vec2,
 }
 
 #[derive(Default)]
@@ -35,10 +36,15 @@ struct MyApp {
 }
 
 impl App for MyApp {
-    fn update(&mut self, state: &mut clod::State) -> Result<(), String> {
+    fn fixed_update(&mut self, state: &mut clod::State) -> Result<(), String> {
+        for entity in self.entities.iter_mut() {
+            let translation = self.rigidbody_set[entity.handle].translation();
+            entity.previous = Vec2::new(translation.x, translation.y);
+        }
+
         let gravity = Vector2::new(0.0, 0.0);
         let integration_parameters = IntegrationParameters {
-            dt: state.delta_seconds(),
+            dt: state.fixed_delta_seconds(),
             ..IntegrationParameters::default()
         };
 
@@ -57,16 +63,19 @@ impl App for MyApp {
             &(),
             &(),
         );
+        Ok(())
+    }
 
+    fn update(&mut self, state: &mut clod::State) -> Result<(), String> {
         for entity in self.entities.iter() {
-            let ball_body = &mut self.rigidbody_set[entity.handle];
+            let ball_body = &self.rigidbody_set[entity.handle];
+            // draw between the last two physics steps
+            let current = Vec2::new(ball_body.translation().x, ball_body.translation().y);
+            let pos = entity.previous.lerp(current, state.interpolation_alpha());
             for collider_handle in ball_body.colliders() {
                 let ball_collider = &self.collider_set[*collider_handle];
                 if let Some(ball) = ball_collider.shape().as_ball() {
-                    state.aa_circle(
-                        Vec2::new(ball_body.translation().x, ball_body.translation().y),
-                        Circle::with_radius(ball.radius).solid(),
-                    );
+                    state.aa_circle(pos, Circle::with_radius(ball.radius).solid());
                 }
             }
         }
@@ -101,6 +110,7 @@ impl App for MyApp {
 
             self.entities.push(Entity {
                 handle: ball_body_handle,
+                previous: bounds / 2.0,
             });
         }
     }
@@ -0,0 +1,82 @@
+use clod::{
+    camera::PerspectiveCamera,
+    mesh::Mesh,
+    style::{CanvasAlignment, MeshStyle, MeshStyleLike, Shading, Stylize},
+    App, AppResult,
+};
+use crossterm::style::Color;
+use glam::{Mat4, Quat, Vec3};
+use rgb::Rgb;
+
+/// Spins a cube, or the OBJ file passed as the first argument.
+struct MyApp {
+    mesh: Mesh,
+    camera: PerspectiveCamera,
+    style: MeshStyle,
+    angle: f32,
+}
+
+impl App for MyApp {
+    fn update(&mut self, state: &mut clod::State) -> Result<(), String> {
+        self.angle += state.delta_seconds();
+        let model = Mat4::from_quat(Quat::from_euler(
+            glam::EulerRot::YXZ,
+            self.angle,
+            self.angle * 0.7,
+            0.0,
+        ));
+        state.mesh(&self.mesh, model, &self.camera, &self.style);
+
+        state.print(
+            format!(
+                "{} faces, space to switch shading, w/s to zoom",
+                self.mesh.faces.len()
+            )
+            .as_str()
+            .align(CanvasAlignment::TOP),
+        );
+        Ok(())
+    }
+
+    fn on_key_event(&mut self, _state: &mut clod::State, event: crossterm::event::KeyEvent) {
+        match event.code {
+            crossterm::event::KeyCode::Char(' ') => {
+                let shading = match self.style.shading {
+                    Shading::Flat => Shading::Wireframe,
+                    Shading::Wireframe => Shading::Flat,
+                };
+                self.style = self.style.shading(shading);
+            }
+            crossterm::event::KeyCode::Char('w') | crossterm::event::KeyCode::Up => {
+                self.camera.position.z = (self.camera.position.z - 0.1).max(1.0);
+            }
+            crossterm::event::KeyCode::Char('s') | crossterm::event::KeyCode::Down => {
+                self.camera.position.z += 0.1;
+            }
+            _ => {}
+        };
+    }
+
+    fn init(&mut self, state: &mut clod::State) -> Result<(), String> {
+        state.set_background_color(Some(Color::Rgb { r: 0, g: 0, b: 0 }));
+        Ok(())
+    }
+}
+
+fn main() -> AppResult {
+    let mesh = match std::env::args().nth(1) {
+        Some(path) => {
+            let data = std::fs::read_to_string(path)?;
+            data.parse()
+                .map_err(|err| clod::AppError::InitError(format!("{err}")))?
+        }
+        None => Mesh::cube(),
+    };
+    let mut app = MyApp {
+        mesh,
+        camera: PerspectiveCamera::looking_at(Vec3::new(0.0, 0.0, 2.5), Vec3::ZERO),
+        style: MeshStyle::flat().color(Rgb::new(120, 200, 255)),
+        angle: 0.0,
+    };
+    app.run()
+}
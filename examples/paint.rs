@@ -0,0 +1,66 @@
+use clod::{
+    config::{AppConfig, AppConfigLike},
+    style::{CanvasAlignment, Circle, CircleLike, Stylize},
+    App, AppResult,
+};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use glam::{IVec2, Vec2};
+use rgb::Rgb;
+
+#[derive(Default)]
+struct MyApp {
+    strokes: Vec<Vec<IVec2>>,
+    cursor: Option<IVec2>,
+}
+
+impl App for MyApp {
+    fn update(&mut self, state: &mut clod::State) -> Result<(), String> {
+        for stroke in &self.strokes {
+            for pair in stroke.windows(2) {
+                state.line_with_color(pair[0], pair[1], crossterm::style::Color::White);
+            }
+            if let [point] = stroke[..] {
+                state.point(point);
+            }
+        }
+
+        if let Some(cursor) = self.cursor {
+            state.aa_circle(
+                cursor.as_vec2() + Vec2::splat(0.5),
+                Circle::with_radius(2.0).stroke_color(Rgb::new(255, 100, 100)),
+            );
+        }
+
+        state.print(
+            "Drag to paint, c to clear"
+                .align(CanvasAlignment::TOP | CanvasAlignment::LEFT)
+                .padding(2),
+        );
+        Ok(())
+    }
+
+    fn on_mouse_event(&mut self, state: &mut clod::State, event: MouseEvent) {
+        let pixel = state.mouse_pixel(&event);
+        self.cursor = Some(pixel);
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => self.strokes.push(vec![pixel]),
+            MouseEventKind::Drag(MouseButton::Left) => {
+                if let Some(stroke) = self.strokes.last_mut() {
+                    stroke.push(pixel);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn on_key_event(&mut self, _state: &mut clod::State, event: KeyEvent) {
+        if let KeyCode::Char('c') = event.code {
+            self.strokes.clear();
+        }
+    }
+}
+
+fn main() -> AppResult {
+    let mut app = MyApp::default();
+    app.run_with(AppConfig::new().mouse_capture(true))
+}
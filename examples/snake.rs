@@ -82,25 +82,17 @@ impl App for MyApp {
 
     fn on_key_event(&mut self, _state: &mut clod::State, event: crossterm::event::KeyEvent) {
         match event.code {
-            crossterm::event::KeyCode::Up => {
-                if self.snake.velocity.y == 0.0 && !self.is_paused {
-                    self.snake.velocity = Vec2::new(0.0, -1.0);
-                }
+            crossterm::event::KeyCode::Up if self.snake.velocity.y == 0.0 && !self.is_paused => {
+                self.snake.velocity = Vec2::new(0.0, -1.0);
             }
-            crossterm::event::KeyCode::Down => {
-                if self.snake.velocity.y == 0.0 && !self.is_paused {
-                    self.snake.velocity = Vec2::new(0.0, 1.0);
-                }
+            crossterm::event::KeyCode::Down if self.snake.velocity.y == 0.0 && !self.is_paused => {
+                self.snake.velocity = Vec2::new(0.0, 1.0);
             }
-            crossterm::event::KeyCode::Left => {
-                if self.snake.velocity.x == 0.0 && !self.is_paused {
-                    self.snake.velocity = Vec2::new(-1.0, 0.0);
-                }
+            crossterm::event::KeyCode::Left if self.snake.velocity.x == 0.0 && !self.is_paused => {
+                self.snake.velocity = Vec2::new(-1.0, 0.0);
             }
-            crossterm::event::KeyCode::Right => {
-                if self.snake.velocity.x == 0.0 && !self.is_paused {
-                    self.snake.velocity = Vec2::new(1.0, 0.0);
-                }
+            crossterm::event::KeyCode::Right if self.snake.velocity.x == 0.0 && !self.is_paused => {
+                self.snake.velocity = Vec2::new(1.0, 0.0);
             }
             crossterm::event::KeyCode::Char('p') => {
                 self.is_paused = !self.is_paused;
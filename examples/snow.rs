@@ -1,7 +1,8 @@
 use std::f32::consts::PI;
 
 use clod::{
-    style::{CanvasAlignment, Circle, CircleLike, Stylize},
+    config::{AppConfig, AppConfigLike},
+    style::{BlendMode, CanvasAlignment, Circle, CircleLike, Stylize},
     App, AppResult,
 };
 use crossterm::style::Color;
@@ -71,11 +72,12 @@ impl App for MyApp {
         self.density = 40.0.lerp(140.0, ease_sin_sq(elapsed, 160.0));
 
         let bounds = state.canvas_size();
+        // overlapping flakes glow brighter
+        state.set_blend_mode(BlendMode::Add);
         for entity in self.entities.iter_mut() {
             if !self.is_paused {
-                entity.vel += self.wind * state.delta_seconds() as f32 * self.wind_speed;
-                entity.pos +=
-                    entity.vel * state.delta_seconds() as f32 * (20.0 + (60.0 * entity.z));
+                entity.vel += self.wind * state.delta_seconds() * self.wind_speed;
+                entity.pos += entity.vel * state.delta_seconds() * (20.0 + (60.0 * entity.z));
             }
             let gray_value = (entity.z * 100.0) as u8 + 40;
 
@@ -86,6 +88,7 @@ impl App for MyApp {
                     .solid(),
             );
         }
+        state.set_blend_mode(BlendMode::Normal);
 
         self.entities.retain(|e| {
             (e.pos.y as u16) < bounds.y + bounds.y
@@ -95,7 +98,7 @@ impl App for MyApp {
         });
 
         if !self.is_paused {
-            self.accumulator += state.delta_seconds() as f32;
+            self.accumulator += state.delta_seconds();
             let available = self.max_entities.saturating_sub(self.entities.len());
             let drops = self.density * self.accumulator;
             self.accumulator = drops.fract() / self.density;
@@ -148,18 +151,18 @@ impl App for MyApp {
         self.density = 20.0;
         self.max_entities = 100;
         self.drop = Vec2 { x: 1.0, y: 1.0 }.normalize();
-        _state.set_background_color(Some(Color::Rgb {
-            r: 30,
-            g: 30,
-            b: 30,
-        }));
         Ok(())
     }
 }
 
 fn main() -> AppResult {
     let mut app = MyApp::default();
-    app.run()
+    let config = AppConfig::new().background_color(Color::Rgb {
+        r: 30,
+        g: 30,
+        b: 30,
+    });
+    app.run_with(config)
 }
 
 #[cfg(test)]
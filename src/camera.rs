@@ -0,0 +1,190 @@
+//! Cameras that map world coordinates onto the canvas.
+
+use glam::{Affine2, Mat4, U16Vec2, Vec2, Vec3, Vec4Swizzles};
+
+/// Looks at a point in the world, with zoom, rotation and screen shake.
+///
+/// When a camera is set on the [`State`](crate::State), every drawing call
+/// takes world coordinates, which the camera maps onto canvas pixels before
+/// the transform stack is applied.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Camera {
+    /// The world position shown in the middle of the canvas.
+    pub position: Vec2,
+    /// How many canvas pixels one world unit covers.
+    pub zoom: f32,
+    /// The rotation of the camera in radians. Turning the camera clockwise
+    /// turns the world anticlockwise on screen.
+    pub rotation: f32,
+    /// An offset in canvas pixels added after everything else, for
+    /// shaking the screen without moving the camera.
+    pub shake: Vec2,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self::looking_at(Vec2::ZERO)
+    }
+}
+
+impl Camera {
+    pub fn looking_at(position: Vec2) -> Self {
+        Self {
+            position,
+            zoom: 1.0,
+            rotation: 0.0,
+            shake: Vec2::ZERO,
+        }
+    }
+
+    /// Returns the transform from world coordinates to canvas pixels for a
+    /// canvas of the given size.
+    pub fn transform(&self, canvas_size: U16Vec2) -> Affine2 {
+        // pixel centres are on whole numbers, so the middle is half a pixel in
+        let center = canvas_size.as_vec2() / 2.0 - Vec2::splat(0.5);
+        Affine2::from_translation(center + self.shake)
+            * Affine2::from_angle(-self.rotation)
+            * Affine2::from_scale(Vec2::splat(self.zoom))
+            * Affine2::from_translation(-self.position)
+    }
+
+    pub fn world_to_screen(&self, pos: Vec2, canvas_size: U16Vec2) -> Vec2 {
+        self.transform(canvas_size).transform_point2(pos)
+    }
+
+    pub fn screen_to_world(&self, pos: Vec2, canvas_size: U16Vec2) -> Vec2 {
+        self.transform(canvas_size).inverse().transform_point2(pos)
+    }
+}
+
+/// Looks at a 3D scene in perspective, for drawing
+/// [`Mesh`](crate::mesh::Mesh)es.
+///
+/// The world is right-handed with y up, so with the default camera x points
+/// right and z points out of the screen.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PerspectiveCamera {
+    pub position: Vec3,
+    /// The point in the middle of the view.
+    pub target: Vec3,
+    pub up: Vec3,
+    /// The vertical field of view in radians.
+    pub fov_y: f32,
+    /// The distance to the near clipping plane. Triangles crossing it are
+    /// not drawn.
+    pub near: f32,
+    /// The distance to the far clipping plane.
+    pub far: f32,
+}
+
+impl Default for PerspectiveCamera {
+    fn default() -> Self {
+        Self::looking_at(Vec3::new(0.0, 0.0, 3.0), Vec3::ZERO)
+    }
+}
+
+impl PerspectiveCamera {
+    /// Creates a camera with a 60 degree field of view.
+    pub fn looking_at(position: Vec3, target: Vec3) -> Self {
+        Self {
+            position,
+            target,
+            up: Vec3::Y,
+            fov_y: 60f32.to_radians(),
+            near: 0.1,
+            far: 100.0,
+        }
+    }
+
+    /// Returns the transform from world coordinates to camera coordinates.
+    pub fn view(&self) -> Mat4 {
+        Mat4::look_at_rh(self.position, self.target, self.up)
+    }
+
+    /// Returns the transform from camera coordinates to clip space for a
+    /// canvas of the given size, with depths from 0 at the near plane to 1
+    /// at the far plane.
+    pub fn projection(&self, canvas_size: U16Vec2) -> Mat4 {
+        let aspect = canvas_size.x as f32 / canvas_size.y.max(1) as f32;
+        Mat4::perspective_rh(self.fov_y, aspect, self.near, self.far)
+    }
+
+    pub fn view_projection(&self, canvas_size: U16Vec2) -> Mat4 {
+        self.projection(canvas_size) * self.view()
+    }
+
+    /// Returns the canvas position of a world point and its depth, or
+    /// `None` if it is outside the near and far planes.
+    pub fn world_to_screen(&self, pos: Vec3, canvas_size: U16Vec2) -> Option<Vec3> {
+        let clip = self.view_projection(canvas_size) * pos.extend(1.0);
+        clip_to_screen(clip.xyz(), clip.w, canvas_size)
+    }
+}
+
+/// Maps a point in clip space to a canvas position and depth, or `None` if
+/// it is outside the near and far planes.
+pub(crate) fn clip_to_screen(clip: Vec3, w: f32, canvas_size: U16Vec2) -> Option<Vec3> {
+    if w <= 0.0 || clip.z < 0.0 || clip.z > w {
+        return None;
+    }
+    let ndc = clip / w;
+    let size = canvas_size.as_vec2();
+    // pixel centres are on whole numbers, and y points down the canvas
+    let x = (ndc.x + 1.0) / 2.0 * size.x - 0.5;
+    let y = (1.0 - ndc.y) / 2.0 * size.y - 0.5;
+    Some(Vec3::new(x, y, ndc.z))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use super::*;
+
+    const SIZE: U16Vec2 = U16Vec2::new(21, 11);
+
+    #[test]
+    fn centred_on_position() {
+        let camera = Camera::looking_at(Vec2::new(100.0, 50.0));
+        assert_eq!(
+            camera.world_to_screen(Vec2::new(100.0, 50.0), SIZE),
+            Vec2::new(10.0, 5.0)
+        );
+        assert_eq!(
+            camera.world_to_screen(Vec2::new(103.0, 50.0), SIZE),
+            Vec2::new(13.0, 5.0)
+        );
+    }
+
+    #[test]
+    fn zoom_rotation_and_shake() {
+        let camera = Camera {
+            zoom: 2.0,
+            rotation: FRAC_PI_2,
+            shake: Vec2::new(1.0, 0.0),
+            ..Default::default()
+        };
+        let screen = camera.world_to_screen(Vec2::new(1.0, 0.0), SIZE);
+        assert!(screen.abs_diff_eq(Vec2::new(11.0, 3.0), 1e-4));
+        let world = camera.screen_to_world(screen, SIZE);
+        assert!(world.abs_diff_eq(Vec2::new(1.0, 0.0), 1e-4));
+    }
+
+    #[test]
+    fn perspective() {
+        let size = U16Vec2::new(20, 20);
+        let camera = PerspectiveCamera::default();
+        let center = camera.world_to_screen(Vec3::ZERO, size).unwrap();
+        assert!(center.truncate().abs_diff_eq(Vec2::splat(9.5), 1e-4));
+
+        // further points are deeper and closer to the middle
+        let near = camera.world_to_screen(Vec3::new(1.0, 1.0, 0.0), size);
+        let far = camera.world_to_screen(Vec3::new(1.0, 1.0, -5.0), size);
+        let (near, far) = (near.unwrap(), far.unwrap());
+        assert!(near.x > far.x && far.x > 9.5);
+        assert!(near.y < far.y && far.y < 9.5);
+        assert!(near.z < far.z);
+
+        assert_eq!(camera.world_to_screen(Vec3::new(0.0, 0.0, 4.0), size), None);
+    }
+}
@@ -0,0 +1,131 @@
+//! Regions that drawing is restricted to.
+
+use glam::{IVec2, UVec2};
+
+/// A pixel surface marking which pixels may be drawn to.
+///
+/// Pixels outside the mask are hidden.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mask {
+    origin: IVec2,
+    size: UVec2,
+    visible: Vec<bool>,
+}
+
+impl Mask {
+    /// Creates a mask covering `size` pixels from `origin`, with every
+    /// pixel hidden.
+    pub fn new(origin: IVec2, size: UVec2) -> Self {
+        Self {
+            origin,
+            size,
+            visible: vec![false; size.x as usize * size.y as usize],
+        }
+    }
+
+    /// Creates a mask with the pixels for which `visible` returns true
+    /// shown.
+    pub fn from_fn(origin: IVec2, size: UVec2, mut visible: impl FnMut(IVec2) -> bool) -> Self {
+        let mut mask = Self::new(origin, size);
+        for y in 0..size.y as i32 {
+            for x in 0..size.x as i32 {
+                let pos = origin + IVec2::new(x, y);
+                mask.set(pos, visible(pos));
+            }
+        }
+        mask
+    }
+
+    pub fn origin(&self) -> IVec2 {
+        self.origin
+    }
+
+    pub fn size(&self) -> UVec2 {
+        self.size
+    }
+
+    /// Shows or hides a pixel. Pixels outside the mask are ignored.
+    pub fn set(&mut self, pos: IVec2, visible: bool) {
+        if let Some(index) = self.index(pos) {
+            self.visible[index] = visible;
+        }
+    }
+
+    pub fn contains(&self, pos: IVec2) -> bool {
+        self.index(pos).is_some_and(|index| self.visible[index])
+    }
+
+    fn index(&self, pos: IVec2) -> Option<usize> {
+        let local = pos - self.origin;
+        if local.cmplt(IVec2::ZERO).any() || local.as_uvec2().cmpge(self.size).any() {
+            return None;
+        }
+        Some(local.y as usize * self.size.x as usize + local.x as usize)
+    }
+}
+
+/// A region of the canvas, in canvas pixels, that drawing is restricted to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Clip {
+    /// The pixels in `min..max`.
+    Rect { min: IVec2, max: IVec2 },
+    /// The visible pixels of a mask.
+    Mask(Mask),
+}
+
+impl Clip {
+    /// The pixels in a rectangle of `size` from `min`. Rectangles reaching
+    /// past `i32::MAX` stop there.
+    pub fn rect(min: IVec2, size: UVec2) -> Self {
+        let size = size.min(UVec2::splat(i32::MAX as u32)).as_ivec2();
+        Self::Rect {
+            min,
+            max: min.saturating_add(size),
+        }
+    }
+
+    pub fn contains(&self, pos: IVec2) -> bool {
+        match self {
+            Self::Rect { min, max } => pos.cmpge(*min).all() && pos.cmplt(*max).all(),
+            Self::Mask(mask) => mask.contains(pos),
+        }
+    }
+}
+
+impl From<Mask> for Clip {
+    fn from(mask: Mask) -> Self {
+        Self::Mask(mask)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rect() {
+        let clip = Clip::rect(IVec2::new(2, 3), UVec2::new(4, 2));
+        assert!(clip.contains(IVec2::new(2, 3)));
+        assert!(clip.contains(IVec2::new(5, 4)));
+        assert!(!clip.contains(IVec2::new(6, 4)));
+        assert!(!clip.contains(IVec2::new(2, 5)));
+        assert!(!clip.contains(IVec2::new(1, 3)));
+
+        let huge = Clip::rect(IVec2::new(i32::MAX - 1, -5), UVec2::MAX);
+        assert!(huge.contains(IVec2::new(i32::MAX - 1, 1000)));
+        assert!(!huge.contains(IVec2::new(i32::MAX - 2, 0)));
+    }
+
+    #[test]
+    fn mask() {
+        let checkers = Mask::from_fn(IVec2::new(-2, -2), UVec2::splat(4), |pos| {
+            (pos.x + pos.y) % 2 == 0
+        });
+        let clip = Clip::from(checkers);
+        assert!(clip.contains(IVec2::ZERO));
+        assert!(!clip.contains(IVec2::new(1, 0)));
+        assert!(clip.contains(IVec2::new(-2, -2)));
+        assert!(!clip.contains(IVec2::new(2, 0)));
+        assert!(!clip.contains(IVec2::new(-3, -3)));
+    }
+}
@@ -0,0 +1,465 @@
+//! Color spaces, hex strings and interpolation between colors.
+//!
+//! Colors are converted to and from `Rgb<u8>` with [`From`], so any color can
+//! be turned into any other by going through RGB. Named terminal colors are
+//! turned into RGB with a [`Palette`](crate::palette::Palette).
+
+use std::fmt;
+
+use crossterm::style::Color;
+use glam::{Mat3, Vec3};
+use rgb::Rgb;
+
+/// Turns an sRGB value from `0.0` to `1.0` into linear light.
+pub fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Turns linear light from `0.0` to `1.0` into an sRGB value.
+pub fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn to_unit(color: Rgb<u8>) -> Vec3 {
+    Vec3::new(color.r as f32, color.g as f32, color.b as f32) / 255.0
+}
+
+fn from_unit(color: Vec3) -> Rgb<u8> {
+    let color = (color.clamp(Vec3::ZERO, Vec3::ONE) * 255.0).round();
+    Rgb::new(color.x as u8, color.y as u8, color.z as u8)
+}
+
+/// Returns the color as a terminal color.
+pub fn to_terminal(color: Rgb<u8>) -> Color {
+    Color::Rgb {
+        r: color.r,
+        g: color.g,
+        b: color.b,
+    }
+}
+
+/// Reads a color written as `#rrggbb` or `#rgb`, with or without the `#`.
+pub fn from_hex(hex: &str) -> Result<Rgb<u8>, HexError> {
+    let digits = hex.strip_prefix('#').unwrap_or(hex);
+    let offset = hex.len() - digits.len();
+    let values = digits
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            c.to_digit(16)
+                .map(|value| value as u8)
+                .ok_or(HexError::InvalidDigit(offset + i))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    match values[..] {
+        [r, g, b] => Ok(Rgb::new(r * 17, g * 17, b * 17)),
+        [r1, r2, g1, g2, b1, b2] => Ok(Rgb::new(r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2)),
+        _ => Err(HexError::InvalidLength),
+    }
+}
+
+/// Writes a color as `#rrggbb`.
+pub fn to_hex(color: Rgb<u8>) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+}
+
+/// An error from reading a hex color.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HexError {
+    /// There were not 3 or 6 digits.
+    InvalidLength,
+    /// The character at the given position is not a hex digit.
+    InvalidDigit(usize),
+}
+
+impl fmt::Display for HexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength => write!(f, "hex colors must have 3 or 6 digits"),
+            Self::InvalidDigit(pos) => write!(f, "invalid hex digit at {pos}"),
+        }
+    }
+}
+
+impl std::error::Error for HexError {}
+
+/// Hue, saturation and lightness, with the hue in degrees and the rest from
+/// `0.0` to `1.0`.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Hsl {
+    pub h: f32,
+    pub s: f32,
+    pub l: f32,
+}
+
+impl Hsl {
+    pub fn new(h: f32, s: f32, l: f32) -> Self {
+        Self { h, s, l }
+    }
+}
+
+/// Hue, saturation and value, with the hue in degrees and the rest from
+/// `0.0` to `1.0`.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Hsv {
+    pub h: f32,
+    pub s: f32,
+    pub v: f32,
+}
+
+impl Hsv {
+    pub fn new(h: f32, s: f32, v: f32) -> Self {
+        Self { h, s, v }
+    }
+}
+
+/// The hue in degrees, and the largest and smallest channels.
+fn hue_and_range(color: Rgb<u8>) -> (f32, f32, f32) {
+    let Vec3 { x: r, y: g, z: b } = to_unit(color);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let range = max - min;
+    let hue = if range == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / range).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / range + 2.0)
+    } else {
+        60.0 * ((r - g) / range + 4.0)
+    };
+    (hue, max, min)
+}
+
+/// Builds a color from a hue in degrees, a chroma and the amount added to
+/// every channel.
+fn from_hue(hue: f32, chroma: f32, offset: f32) -> Rgb<u8> {
+    let hue = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+    let (r, g, b) = match hue as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    from_unit(Vec3::new(r, g, b) + offset)
+}
+
+impl From<Rgb<u8>> for Hsl {
+    fn from(color: Rgb<u8>) -> Self {
+        let (h, max, min) = hue_and_range(color);
+        let l = (max + min) / 2.0;
+        let s = if max == min {
+            0.0
+        } else {
+            (max - min) / (1.0 - (2.0 * l - 1.0).abs())
+        };
+        Self { h, s, l }
+    }
+}
+
+impl From<Hsl> for Rgb<u8> {
+    fn from(color: Hsl) -> Self {
+        let chroma = (1.0 - (2.0 * color.l - 1.0).abs()) * color.s;
+        from_hue(color.h, chroma, color.l - chroma / 2.0)
+    }
+}
+
+impl From<Rgb<u8>> for Hsv {
+    fn from(color: Rgb<u8>) -> Self {
+        let (h, max, min) = hue_and_range(color);
+        let s = if max == 0.0 { 0.0 } else { (max - min) / max };
+        Self { h, s, v: max }
+    }
+}
+
+impl From<Hsv> for Rgb<u8> {
+    fn from(color: Hsv) -> Self {
+        let chroma = color.v * color.s;
+        from_hue(color.h, chroma, color.v - chroma)
+    }
+}
+
+/// A perceptual color space where equal steps look like equal changes,
+/// with lightness `l` from `0.0` to `1.0` and the axes `a` from green to red
+/// and `b` from blue to yellow.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Oklab {
+    pub l: f32,
+    pub a: f32,
+    pub b: f32,
+}
+
+impl Oklab {
+    pub fn new(l: f32, a: f32, b: f32) -> Self {
+        Self { l, a, b }
+    }
+}
+
+/// [`Oklab`] as lightness, chroma and a hue in degrees.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Oklch {
+    pub l: f32,
+    pub c: f32,
+    pub h: f32,
+}
+
+impl Oklch {
+    pub fn new(l: f32, c: f32, h: f32) -> Self {
+        Self { l, c, h }
+    }
+}
+
+// the matrices from https://bottosson.github.io/posts/oklab/, by column, kept
+// exactly as published
+#[allow(clippy::excessive_precision)]
+const LINEAR_TO_LMS: Mat3 = Mat3::from_cols(
+    Vec3::new(0.4122214708, 0.2119034982, 0.0883024619),
+    Vec3::new(0.5363325363, 0.6806995451, 0.2817188376),
+    Vec3::new(0.0514459929, 0.1073969566, 0.6299787005),
+);
+#[allow(clippy::excessive_precision)]
+const LMS_TO_OKLAB: Mat3 = Mat3::from_cols(
+    Vec3::new(0.2104542553, 1.9779984951, 0.0259040371),
+    Vec3::new(0.7936177850, -2.4285922050, 0.7827717662),
+    Vec3::new(-0.0040720468, 0.4505937099, -0.8086757660),
+);
+#[allow(clippy::excessive_precision)]
+const OKLAB_TO_LMS: Mat3 = Mat3::from_cols(
+    Vec3::new(1.0, 1.0, 1.0),
+    Vec3::new(0.3963377774, -0.1055613458, -0.0894841775),
+    Vec3::new(0.2158037573, -0.0638541728, -1.2914855480),
+);
+#[allow(clippy::excessive_precision)]
+const LMS_TO_LINEAR: Mat3 = Mat3::from_cols(
+    Vec3::new(4.0767416621, -1.2684380046, -0.0041960863),
+    Vec3::new(-3.3077115913, 2.6097574011, -0.7034186147),
+    Vec3::new(0.2309699292, -0.3413193965, 1.7076147010),
+);
+
+impl From<Rgb<u8>> for Oklab {
+    fn from(color: Rgb<u8>) -> Self {
+        let linear = to_unit(color).to_array().map(srgb_to_linear);
+        let lms = (LINEAR_TO_LMS * Vec3::from(linear))
+            .to_array()
+            .map(f32::cbrt);
+        let Vec3 { x: l, y: a, z: b } = LMS_TO_OKLAB * Vec3::from(lms);
+        Self { l, a, b }
+    }
+}
+
+impl From<Oklab> for Rgb<u8> {
+    fn from(color: Oklab) -> Self {
+        let lms = OKLAB_TO_LMS * Vec3::new(color.l, color.a, color.b);
+        let linear = LMS_TO_LINEAR * lms.powf(3.0);
+        let srgb = linear
+            .clamp(Vec3::ZERO, Vec3::ONE)
+            .to_array()
+            .map(linear_to_srgb);
+        from_unit(Vec3::from(srgb))
+    }
+}
+
+impl From<Oklab> for Oklch {
+    fn from(color: Oklab) -> Self {
+        Self {
+            l: color.l,
+            c: color.a.hypot(color.b),
+            h: color.b.atan2(color.a).to_degrees().rem_euclid(360.0),
+        }
+    }
+}
+
+impl From<Oklch> for Oklab {
+    fn from(color: Oklch) -> Self {
+        let (sin, cos) = color.h.to_radians().sin_cos();
+        Self {
+            l: color.l,
+            a: color.c * cos,
+            b: color.c * sin,
+        }
+    }
+}
+
+impl From<Rgb<u8>> for Oklch {
+    fn from(color: Rgb<u8>) -> Self {
+        Oklab::from(color).into()
+    }
+}
+
+impl From<Oklch> for Rgb<u8> {
+    fn from(color: Oklch) -> Self {
+        Oklab::from(color).into()
+    }
+}
+
+/// The color space colors are mixed in.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// The sRGB values are mixed directly.
+    #[default]
+    Srgb,
+    /// Amounts of light are mixed, which keeps mixes bright.
+    LinearSrgb,
+    /// Mixes go around the color wheel, taking the shorter way.
+    Hsl,
+    Hsv,
+    /// Mixes change evenly to the eye, without muddy middles.
+    Oklab,
+    /// Like [`Oklab`](Self::Oklab), but keeping colors saturated by going
+    /// around the hue wheel.
+    Oklch,
+}
+
+fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    from + (to - from) * t
+}
+
+/// Mixes hues in degrees the shorter way around. Hues of grey colors are
+/// meaningless, so the other hue is used.
+fn lerp_hue(from: (f32, bool), to: (f32, bool), t: f32) -> f32 {
+    match (from, to) {
+        ((_, true), (hue, _)) | ((hue, _), (_, true)) => hue,
+        ((from, _), (to, _)) => {
+            let delta = (to - from + 180.0).rem_euclid(360.0) - 180.0;
+            (from + delta * t).rem_euclid(360.0)
+        }
+    }
+}
+
+/// Returns the color `t` of the way from `from` to `to`, mixed in `space`.
+pub fn interpolate(from: Rgb<u8>, to: Rgb<u8>, t: f32, space: ColorSpace) -> Rgb<u8> {
+    const GREY: f32 = 1e-4;
+    match space {
+        ColorSpace::Srgb => from_unit(to_unit(from).lerp(to_unit(to), t)),
+        ColorSpace::LinearSrgb => {
+            let linear = |color| Vec3::from(to_unit(color).to_array().map(srgb_to_linear));
+            let mixed = linear(from).lerp(linear(to), t);
+            from_unit(Vec3::from(mixed.to_array().map(linear_to_srgb)))
+        }
+        ColorSpace::Hsl => {
+            let (from, to) = (Hsl::from(from), Hsl::from(to));
+            Hsl {
+                h: lerp_hue((from.h, from.s < GREY), (to.h, to.s < GREY), t),
+                s: lerp(from.s, to.s, t),
+                l: lerp(from.l, to.l, t),
+            }
+            .into()
+        }
+        ColorSpace::Hsv => {
+            let (from, to) = (Hsv::from(from), Hsv::from(to));
+            Hsv {
+                h: lerp_hue((from.h, from.s < GREY), (to.h, to.s < GREY), t),
+                s: lerp(from.s, to.s, t),
+                v: lerp(from.v, to.v, t),
+            }
+            .into()
+        }
+        ColorSpace::Oklab => {
+            let (from, to) = (Oklab::from(from), Oklab::from(to));
+            Oklab {
+                l: lerp(from.l, to.l, t),
+                a: lerp(from.a, to.a, t),
+                b: lerp(from.b, to.b, t),
+            }
+            .into()
+        }
+        ColorSpace::Oklch => {
+            let (from, to) = (Oklch::from(from), Oklch::from(to));
+            Oklch {
+                l: lerp(from.l, to.l, t),
+                c: lerp(from.c, to.c, t),
+                h: lerp_hue((from.h, from.c < GREY), (to.h, to.c < GREY), t),
+            }
+            .into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: Rgb<u8> = Rgb::new(255, 0, 0);
+    const BLUE: Rgb<u8> = Rgb::new(0, 0, 255);
+    const BLACK: Rgb<u8> = Rgb::new(0, 0, 0);
+    const WHITE: Rgb<u8> = Rgb::new(255, 255, 255);
+
+    #[test]
+    fn hex() {
+        assert_eq!(from_hex("#336699"), Ok(Rgb::new(0x33, 0x66, 0x99)));
+        assert_eq!(from_hex("fA0"), Ok(Rgb::new(0xff, 0xaa, 0x00)));
+        assert_eq!(from_hex("#12345"), Err(HexError::InvalidLength));
+        assert_eq!(from_hex("#12g456"), Err(HexError::InvalidDigit(3)));
+        assert_eq!(to_hex(Rgb::new(1, 171, 255)), "#01abff");
+    }
+
+    #[test]
+    fn hsl_and_hsv() {
+        assert_eq!(Hsl::from(RED), Hsl::new(0.0, 1.0, 0.5));
+        assert_eq!(Hsv::from(BLUE), Hsv::new(240.0, 1.0, 1.0));
+        assert_eq!(Hsl::from(Rgb::new(128, 128, 128)).s, 0.0);
+        assert_eq!(Rgb::from(Hsl::new(120.0, 1.0, 0.25)), Rgb::new(0, 128, 0));
+        assert_eq!(
+            Rgb::from(Hsv::new(-60.0, 0.5, 1.0)),
+            Rgb::new(255, 128, 255)
+        );
+    }
+
+    #[test]
+    fn oklab() {
+        let red = Oklab::from(RED);
+        assert!((red.l - 0.62796).abs() < 1e-3);
+        assert!((red.a - 0.22486).abs() < 1e-3);
+        assert!((red.b - 0.12585).abs() < 1e-3);
+        let white = Oklch::from(WHITE);
+        assert!((white.l - 1.0).abs() < 1e-3 && white.c < 1e-3);
+    }
+
+    #[test]
+    fn round_trips() {
+        for color in [
+            RED,
+            BLUE,
+            BLACK,
+            WHITE,
+            Rgb::new(12, 200, 99),
+            Rgb::new(250, 3, 140),
+        ] {
+            assert_eq!(Rgb::from(Hsl::from(color)), color);
+            assert_eq!(Rgb::from(Hsv::from(color)), color);
+            assert_eq!(Rgb::from(Oklab::from(color)), color);
+            assert_eq!(Rgb::from(Oklch::from(color)), color);
+        }
+    }
+
+    #[test]
+    fn interpolation() {
+        let middle = |space| interpolate(RED, BLUE, 0.5, space);
+        assert_eq!(middle(ColorSpace::Srgb), Rgb::new(128, 0, 128));
+        assert_eq!(middle(ColorSpace::LinearSrgb), Rgb::new(188, 0, 188));
+        assert_eq!(middle(ColorSpace::Hsl), Rgb::new(255, 0, 255));
+        assert_eq!(middle(ColorSpace::Hsv), Rgb::new(255, 0, 255));
+
+        // half way in lightness is not half the light
+        let grey = interpolate(BLACK, WHITE, 0.5, ColorSpace::Oklab);
+        assert_eq!(grey, Rgb::new(99, 99, 99));
+        // greys keep the hue of the other color
+        let pink = interpolate(WHITE, RED, 0.5, ColorSpace::Oklch);
+        assert!((Oklch::from(pink).h - Oklch::from(RED).h).abs() < 2.0);
+        for space in [ColorSpace::Oklab, ColorSpace::Oklch, ColorSpace::Hsl] {
+            assert_eq!(interpolate(RED, BLUE, 0.0, space), RED);
+            assert_eq!(interpolate(RED, BLUE, 1.0, space), BLUE);
+        }
+    }
+}
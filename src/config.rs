@@ -0,0 +1,274 @@
+//! Options for how [`App::run_with`](crate::App::run_with) sets up the
+//! terminal and runs the loop.
+
+use std::time::Duration;
+
+use crossterm::{
+    event::{KeyCode, KeyEvent, KeyModifiers},
+    style::Color,
+};
+use glam::U16Vec2;
+
+/// A key together with the modifiers that have to be held with it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// A key pressed without modifiers.
+    pub const fn new(code: KeyCode) -> Self {
+        Self {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    /// A character pressed while holding control.
+    pub const fn ctrl(c: char) -> Self {
+        Self {
+            code: KeyCode::Char(c),
+            modifiers: KeyModifiers::CONTROL,
+        }
+    }
+
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        self.code == event.code && self.modifiers == event.modifiers
+    }
+}
+
+impl From<KeyCode> for KeyBinding {
+    fn from(code: KeyCode) -> Self {
+        Self::new(code)
+    }
+}
+
+/// How the app runs, passed to [`App::run_with`](crate::App::run_with).
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppConfig {
+    /// How many frames are drawn every second at most, or `None` to draw
+    /// them as fast as possible.
+    pub target_fps: Option<f32>,
+    /// How many times [`App::fixed_update`](crate::App::fixed_update) runs
+    /// every second. A rate of zero or less turns fixed updates off.
+    pub tick_rate: f32,
+    /// The most ticks run before a frame is drawn. When the app falls
+    /// further behind, the time it cannot catch up on is dropped.
+    pub max_ticks_per_frame: u32,
+    /// The keys that quit the app.
+    pub quit_keys: Vec<KeyBinding>,
+    /// The background color the canvas starts with.
+    pub background_color: Option<Color>,
+    /// Whether the terminal reports mouse events.
+    pub mouse_capture: bool,
+    /// Whether mouse events are reported in screen pixels (SGR-pixel mode)
+    /// instead of cells, so [`State::mouse_pixel`](crate::State::mouse_pixel)
+    /// can tell the two pixels of a cell apart. Only used with
+    /// [`mouse_capture`](Self::mouse_capture), and only when the terminal
+    /// says how big it is in pixels. Terminals without SGR-pixel mode keep
+    /// reporting cells, which are then read as pixels, so only turn this on
+    /// for terminals known to support it.
+    pub pixel_mouse: bool,
+    /// Whether the terminal reports when it gains and loses focus.
+    pub focus_events: bool,
+    /// Whether pasted text arrives all at once instead of as key presses.
+    pub bracketed_paste: bool,
+    /// Whether the app draws on the alternate screen, leaving the terminal
+    /// as it was when it quits.
+    pub alternate_screen: bool,
+    /// The fewest columns and rows the app draws in. When the terminal is
+    /// smaller, a message asking for a bigger terminal is shown instead of
+    /// updating the app.
+    pub min_size: Option<U16Vec2>,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            target_fps: Some(60.0),
+            tick_rate: 60.0,
+            max_ticks_per_frame: 5,
+            quit_keys: vec![
+                KeyBinding::new(KeyCode::Char('q')),
+                KeyBinding::new(KeyCode::Esc),
+                KeyBinding::ctrl('c'),
+            ],
+            background_color: None,
+            mouse_capture: false,
+            pixel_mouse: false,
+            focus_events: false,
+            bracketed_paste: false,
+            alternate_screen: true,
+            min_size: None,
+        }
+    }
+}
+
+impl AppConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns how long a frame takes at the target frame rate.
+    pub fn frame_time(&self) -> Duration {
+        self.target_fps
+            .filter(|fps| *fps > 0.0)
+            .map_or(Duration::ZERO, |fps| {
+                Duration::try_from_secs_f32(1.0 / fps).unwrap_or(Duration::MAX)
+            })
+    }
+
+    /// Returns how long a tick of the fixed update takes, or zero when fixed
+    /// updates are turned off.
+    pub fn tick_time(&self) -> Duration {
+        if self.tick_rate > 0.0 {
+            Duration::try_from_secs_f32(1.0 / self.tick_rate).unwrap_or(Duration::MAX)
+        } else {
+            Duration::ZERO
+        }
+    }
+
+    /// Returns whether `event` quits the app.
+    pub fn is_quit_key(&self, event: &KeyEvent) -> bool {
+        self.quit_keys.iter().any(|key| key.matches(event))
+    }
+}
+
+pub trait AppConfigLike: Sized + AsMut<AppConfig> + AsRef<AppConfig> {
+    type Like: AsMut<AppConfig> + AsRef<AppConfig>;
+
+    fn like(self) -> Self::Like;
+
+    fn target_fps(self, fps: f32) -> Self::Like {
+        let mut config = self.like();
+        config.as_mut().target_fps = Some(fps);
+        config
+    }
+
+    fn uncapped(self) -> Self::Like {
+        let mut config = self.like();
+        config.as_mut().target_fps = None;
+        config
+    }
+
+    fn tick_rate(self, rate: f32) -> Self::Like {
+        let mut config = self.like();
+        config.as_mut().tick_rate = rate;
+        config
+    }
+
+    fn max_ticks_per_frame(self, ticks: u32) -> Self::Like {
+        let mut config = self.like();
+        config.as_mut().max_ticks_per_frame = ticks;
+        config
+    }
+
+    fn quit_keys(self, keys: impl IntoIterator<Item = KeyBinding>) -> Self::Like {
+        let mut config = self.like();
+        config.as_mut().quit_keys = keys.into_iter().collect();
+        config
+    }
+
+    fn background_color(self, color: Color) -> Self::Like {
+        let mut config = self.like();
+        config.as_mut().background_color = Some(color);
+        config
+    }
+
+    fn mouse_capture(self, capture: bool) -> Self::Like {
+        let mut config = self.like();
+        config.as_mut().mouse_capture = capture;
+        config
+    }
+
+    fn pixel_mouse(self, pixels: bool) -> Self::Like {
+        let mut config = self.like();
+        config.as_mut().pixel_mouse = pixels;
+        config
+    }
+
+    fn focus_events(self, report: bool) -> Self::Like {
+        let mut config = self.like();
+        config.as_mut().focus_events = report;
+        config
+    }
+
+    fn bracketed_paste(self, bracketed: bool) -> Self::Like {
+        let mut config = self.like();
+        config.as_mut().bracketed_paste = bracketed;
+        config
+    }
+
+    fn alternate_screen(self, alternate: bool) -> Self::Like {
+        let mut config = self.like();
+        config.as_mut().alternate_screen = alternate;
+        config
+    }
+
+    fn min_size(self, size: U16Vec2) -> Self::Like {
+        let mut config = self.like();
+        config.as_mut().min_size = Some(size);
+        config
+    }
+}
+
+impl AsRef<AppConfig> for AppConfig {
+    fn as_ref(&self) -> &AppConfig {
+        self
+    }
+}
+
+impl AsMut<AppConfig> for AppConfig {
+    fn as_mut(&mut self) -> &mut AppConfig {
+        self
+    }
+}
+
+impl AppConfigLike for AppConfig {
+    type Like = AppConfig;
+
+    fn like(self) -> Self::Like {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quit_keys() {
+        let config = AppConfig::new();
+        assert!(config.is_quit_key(&KeyEvent::from(KeyCode::Esc)));
+        assert!(config.is_quit_key(&KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)));
+        assert!(!config.is_quit_key(&KeyEvent::from(KeyCode::Char('c'))));
+
+        let config = config.quit_keys([KeyBinding::ctrl('x')]);
+        assert!(!config.is_quit_key(&KeyEvent::from(KeyCode::Char('q'))));
+        assert!(config.is_quit_key(&KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL)));
+    }
+
+    #[test]
+    fn frame_time() {
+        assert_eq!(
+            AppConfig::new().target_fps(50.0).frame_time(),
+            Duration::from_millis(20)
+        );
+        assert_eq!(AppConfig::new().uncapped().frame_time(), Duration::ZERO);
+        assert_eq!(
+            AppConfig::new().target_fps(1e-20).frame_time(),
+            Duration::MAX
+        );
+    }
+
+    #[test]
+    fn tick_time() {
+        assert_eq!(
+            AppConfig::new().tick_rate(50.0).tick_time(),
+            Duration::from_millis(20)
+        );
+        assert_eq!(AppConfig::new().tick_rate(0.0).tick_time(), Duration::ZERO);
+        assert_eq!(AppConfig::new().tick_rate(1e-20).tick_time(), Duration::MAX);
+    }
+}
@@ -0,0 +1,90 @@
+use crossterm::style::Color;
+use glam::{U16Vec2, Vec2};
+use rgb::Rgb;
+
+use crate::{color::linear_to_srgb, style::Ascii};
+
+use super::{gamma::srgb_to_linear, SimpleCanvas};
+
+impl SimpleCanvas {
+    /// Replaces the half blocks on the canvas with characters from the ramp
+    /// of `ascii`.
+    pub(super) fn encode_ascii(&mut self, ascii: &Ascii) {
+        let size = self.renderer.size();
+        let width = size.x as usize;
+        let default = self.renderer.get_background_color();
+        let pixel = |pos: U16Vec2| {
+            self.color_at(pos)
+                .or(default)
+                .and_then(|color| self.palette.to_rgb(color))
+        };
+
+        // the color and lightness of every cell
+        let cells: Vec<Option<(Rgb<u8>, f32)>> = (0..size.y)
+            .flat_map(|y| (0..size.x).map(move |x| U16Vec2::new(x, y)))
+            .map(|pos| {
+                let top = pixel(U16Vec2::new(pos.x, pos.y * 2));
+                let bottom = pixel(U16Vec2::new(pos.x, pos.y * 2 + 1));
+                let colors: Vec<Rgb<u8>> = top.into_iter().chain(bottom).collect();
+                if colors.is_empty() {
+                    return None;
+                }
+                // pixels without a color are as dark as the terminal
+                let linear = colors.iter().map(|color| luminance(*color)).sum::<f32>() / 2.0;
+                let average = |channel: fn(&Rgb<u8>) -> u8| {
+                    let sum: u16 = colors.iter().map(|color| channel(color) as u16).sum();
+                    (sum / colors.len() as u16) as u8
+                };
+                let color = Rgb::new(average(|c| c.r), average(|c| c.g), average(|c| c.b));
+                Some((color, linear_to_srgb(linear)))
+            })
+            .collect();
+        let lightness_at = |x: i32, y: i32| {
+            let x = x.clamp(0, size.x as i32 - 1) as usize;
+            let y = y.clamp(0, size.y as i32 - 1) as usize;
+            cells[y * width + x].map_or(0.0, |(_, lightness)| lightness)
+        };
+
+        for (index, cell) in self.renderer.cells_mut().iter_mut().enumerate() {
+            if cell.text {
+                continue;
+            }
+            let (x, y) = ((index % width) as i32, (index / width) as i32);
+            // a sobel filter, with rows twice as far apart as columns
+            let gradient = Vec2::new(
+                lightness_at(x + 1, y - 1)
+                    + 2.0 * lightness_at(x + 1, y)
+                    + lightness_at(x + 1, y + 1)
+                    - lightness_at(x - 1, y - 1)
+                    - 2.0 * lightness_at(x - 1, y)
+                    - lightness_at(x - 1, y + 1),
+                (lightness_at(x - 1, y + 1)
+                    + 2.0 * lightness_at(x, y + 1)
+                    + lightness_at(x + 1, y + 1)
+                    - lightness_at(x - 1, y - 1)
+                    - 2.0 * lightness_at(x, y - 1)
+                    - lightness_at(x + 1, y - 1))
+                    / 2.0,
+            ) / 4.0;
+            cell.c = match ascii.edge_threshold {
+                Some(threshold) if gradient.length() > threshold => {
+                    Ascii::edge(gradient.y.atan2(gradient.x))
+                }
+                _ if cells[index].is_none() => ' ',
+                _ => ascii.shade(lightness_at(x, y)),
+            };
+            // edges next to empty cells take the color of the terminal
+            cell.style.foreground_color = cells[index]
+                .filter(|_| ascii.colored)
+                .map(|(Rgb { r, g, b }, _)| Color::Rgb { r, g, b });
+            cell.style.background_color = None;
+        }
+    }
+}
+
+/// Returns the relative luminance of a color, in linear light.
+fn luminance(color: Rgb<u8>) -> f32 {
+    0.2126 * srgb_to_linear(color.r)
+        + 0.7152 * srgb_to_linear(color.g)
+        + 0.0722 * srgb_to_linear(color.b)
+}
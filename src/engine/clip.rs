@@ -0,0 +1,93 @@
+use glam::{IVec2, U16Vec2};
+
+use crate::clip::{Clip, Mask};
+
+use super::SimpleCanvas;
+
+impl SimpleCanvas {
+    /// Whether a pixel is inside every region on the clip stack.
+    pub(super) fn is_visible(&self, pos: U16Vec2) -> bool {
+        let pos = pos.as_ivec2();
+        self.clips.iter().all(|clip| clip.contains(pos))
+    }
+
+    /// Restricts drawing to the region, on top of any regions already on
+    /// the stack.
+    pub fn push_clip(&mut self, clip: impl Into<Clip>) {
+        self.clips.push(clip.into());
+    }
+
+    pub fn pop_clip(&mut self) -> Option<Clip> {
+        self.clips.pop()
+    }
+
+    pub fn reset_clip(&mut self) {
+        self.clips.clear();
+    }
+
+    /// Returns a mask showing every pixel that has been drawn to.
+    pub fn drawn_mask(&self) -> Mask {
+        let size = self.size();
+        Mask::from_fn(IVec2::ZERO, size.as_uvec2(), |pos| self.at(pos).is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::Color;
+    use glam::{UVec2, Vec2};
+    use rgb::Rgb;
+
+    use super::*;
+    use crate::style::{Rect, RectLike, Stylize};
+
+    #[test]
+    fn clipped_drawing() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(10, 5));
+        canvas.push_clip(Clip::rect(IVec2::new(2, 2), UVec2::new(4, 4)));
+        canvas.fill_rect(Vec2::ZERO, Rect::square(10.0));
+        assert!(canvas.at(IVec2::new(2, 2)).is_some());
+        assert!(canvas.at(IVec2::new(5, 5)).is_some());
+        assert!(canvas.at(IVec2::new(1, 2)).is_none());
+        assert!(canvas.at(IVec2::new(6, 5)).is_none());
+
+        // regions on the stack intersect
+        canvas.push_clip(Clip::rect(IVec2::new(7, 0), UVec2::new(2, 2)));
+        canvas.point(IVec2::new(7, 1));
+        assert!(canvas.at(IVec2::new(7, 1)).is_none());
+
+        canvas.reset_clip();
+        canvas.point(IVec2::new(7, 1));
+        assert!(canvas.at(IVec2::new(7, 1)).is_some());
+    }
+
+    #[test]
+    fn masks() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(4, 2));
+        canvas.point(IVec2::new(1, 1));
+        canvas.point(IVec2::new(2, 2));
+        let mask = canvas.drawn_mask();
+
+        canvas.push_clip(mask);
+        canvas.fill_rect(
+            Vec2::ZERO,
+            Rect::square(4.0).stroke_color(Rgb::new(255, 0, 0)),
+        );
+        let red = Some(Color::Rgb { r: 255, g: 0, b: 0 });
+        assert_eq!(canvas.at(IVec2::new(1, 1)), red);
+        assert_eq!(canvas.at(IVec2::new(2, 2)), red);
+        assert_eq!(canvas.at(IVec2::new(0, 0)), None);
+    }
+
+    #[test]
+    fn clipped_text() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(10, 2));
+        canvas.point(IVec2::new(4, 2));
+        canvas.point(IVec2::new(5, 2));
+        canvas.push_clip(Clip::rect(IVec2::ZERO, UVec2::new(5, 4)));
+        // centred text lands on the cells at x = 4 and x = 5
+        canvas.print("ab".stylize());
+        assert_eq!(canvas.at(IVec2::new(4, 2)), None);
+        assert_eq!(canvas.at(IVec2::new(5, 2)), Some(Color::White));
+    }
+}
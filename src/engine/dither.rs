@@ -0,0 +1,103 @@
+use glam::{U16Vec2, Vec3};
+use rgb::Rgb;
+
+use crate::palette::{ColorMode, Dither};
+
+use super::SimpleCanvas;
+
+/// A 4x4 Bayer matrix, with thresholds from 0 to 15.
+const BAYER: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Where the error of a pixel goes, as offsets and weights.
+const FLOYD_STEINBERG: &[(i32, i32, f32)] = &[
+    (1, 0, 7.0 / 16.0),
+    (-1, 1, 3.0 / 16.0),
+    (0, 1, 5.0 / 16.0),
+    (1, 1, 1.0 / 16.0),
+];
+const ATKINSON: &[(i32, i32, f32)] = &[
+    (1, 0, 1.0 / 8.0),
+    (2, 0, 1.0 / 8.0),
+    (-1, 1, 1.0 / 8.0),
+    (0, 1, 1.0 / 8.0),
+    (1, 1, 1.0 / 8.0),
+    (0, 2, 1.0 / 8.0),
+];
+
+impl SimpleCanvas {
+    /// Reduces every color on the canvas to the colors of the color mode,
+    /// dithering pixels and taking the nearest color for text.
+    pub(super) fn reduce_colors(&mut self) {
+        if self.color_mode == ColorMode::TrueColor {
+            return;
+        }
+
+        let size = self.size();
+        let width = size.x as usize;
+        let mut pixels: Vec<Option<Vec3>> = (0..size.y)
+            .flat_map(|y| (0..size.x).map(move |x| U16Vec2::new(x, y)))
+            .map(|pos| {
+                self.color_at(pos)
+                    .and_then(|color| self.palette.to_rgb(color))
+                    .map(|Rgb { r, g, b }| Vec3::new(r as f32, g as f32, b as f32))
+            })
+            .collect();
+
+        // roughly the distance between neighbouring palette colors
+        let spread = match self.color_mode {
+            ColorMode::Ansi16 => 128.0,
+            _ => 40.0,
+        };
+        for index in 0..pixels.len() {
+            let Some(color) = pixels[index] else {
+                continue;
+            };
+            let (x, y) = (index % width, index / width);
+            let wanted = match self.dither {
+                Dither::Bayer => {
+                    let threshold = (BAYER[y % 4][x % 4] as f32 + 0.5) / 16.0 - 0.5;
+                    color + Vec3::splat(threshold * spread)
+                }
+                _ => color,
+            };
+            let wanted = wanted.clamp(Vec3::ZERO, Vec3::splat(255.0)).round();
+            let wanted = Rgb::new(wanted.x as u8, wanted.y as u8, wanted.z as u8);
+            let index_color = self.palette.nearest(wanted, self.color_mode);
+
+            let diffusion = match self.dither {
+                Dither::FloydSteinberg => FLOYD_STEINBERG,
+                Dither::Atkinson => ATKINSON,
+                _ => &[],
+            };
+            let shown = self.palette.ansi_value(index_color);
+            let error = color - Vec3::new(shown.r as f32, shown.g as f32, shown.b as f32);
+            for (dx, dy, weight) in diffusion {
+                let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+                if nx < 0 || nx >= size.x as i32 || ny >= size.y as i32 {
+                    continue;
+                }
+                if let Some(neighbour) = &mut pixels[ny as usize * width + nx as usize] {
+                    *neighbour += error * *weight;
+                }
+            }
+
+            let pos = U16Vec2::new(x as u16, y as u16);
+            self.set_pixel(pos, Some(crossterm::style::Color::AnsiValue(index_color)));
+        }
+
+        // text and the background are not dithered
+        let (palette, mode) = (self.palette, self.color_mode);
+        let reduce = |color: &mut Option<crossterm::style::Color>| {
+            if let Some(rgb) = color.and_then(|color| palette.to_rgb(color)) {
+                if !matches!(color, Some(crossterm::style::Color::AnsiValue(_))) {
+                    *color = Some(palette.quantize(rgb, mode));
+                }
+            }
+        };
+        for cell in self.renderer.cells_mut() {
+            reduce(&mut cell.style.foreground_color);
+            reduce(&mut cell.style.background_color);
+            reduce(&mut cell.style.underline_color);
+        }
+    }
+}
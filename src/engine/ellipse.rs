@@ -0,0 +1,76 @@
+use std::f32::consts::TAU;
+
+use glam::Vec2;
+
+use crate::style::{Arc, ArcKind, Ellipse, FillRule, Stroke};
+
+use super::{
+    shape::{arc_points, ellipse_points},
+    stroke::stroke_outline,
+    SimpleCanvas,
+};
+
+impl SimpleCanvas {
+    pub(super) fn draw_ellipse(&mut self, pos: Vec2, ellipse: Ellipse) {
+        if ellipse.radii.min_element() <= 0.0 {
+            return;
+        }
+
+        let paint = ellipse.stroke_color.unwrap_or_default();
+        let mut outline = ellipse_points(pos, ellipse.radii, ellipse.rotation, 0.0, TAU);
+        outline.pop();
+
+        if ellipse.solid {
+            self.paint_contours(&[outline], FillRule::NonZero, &paint);
+        } else {
+            let stroke = Stroke::with_width(ellipse.stroke_width.unwrap_or(1.0));
+            let contours = stroke_outline(&outline, true, &stroke);
+            self.paint_contours(&contours, FillRule::NonZero, &paint);
+        }
+    }
+
+    pub(super) fn draw_stroked_circle(&mut self, pos: Vec2, radius: f32, stroke: &Stroke) {
+        if radius <= 0.0 {
+            return;
+        }
+
+        let mut outline = arc_points(pos, radius, 0.0, TAU);
+        outline.pop();
+        self.draw_polyline(&outline, true, stroke);
+    }
+
+    pub(super) fn draw_arc(&mut self, pos: Vec2, arc: Arc) {
+        if arc.radius <= 0.0 || arc.start_angle == arc.end_angle {
+            return;
+        }
+
+        let paint = arc.stroke_color.unwrap_or_default();
+        let outer = arc_points(pos, arc.radius, arc.start_angle, arc.end_angle);
+
+        let contours = match arc.kind {
+            ArcKind::Stroke => {
+                let stroke = Stroke::with_width(arc.stroke_width.unwrap_or(1.0));
+                stroke_outline(&outer, false, &stroke)
+            }
+            ArcKind::Pie => {
+                let mut wedge = vec![pos];
+                wedge.extend(outer);
+                vec![wedge]
+            }
+            ArcKind::Donut { inner_radius } => {
+                let mut band = outer;
+                if inner_radius > 0.0 {
+                    band.extend(
+                        arc_points(pos, inner_radius, arc.start_angle, arc.end_angle)
+                            .into_iter()
+                            .rev(),
+                    );
+                } else {
+                    band.push(pos);
+                }
+                vec![band]
+            }
+        };
+        self.paint_contours(&contours, FillRule::NonZero, &paint);
+    }
+}
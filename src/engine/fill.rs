@@ -0,0 +1,142 @@
+use glam::IVec2;
+
+use crate::style::{Connectivity, FloodFill};
+
+use super::SimpleCanvas;
+
+impl SimpleCanvas {
+    /// Fills the region connected to `pos` one horizontal span at a time.
+    pub(super) fn draw_flood_fill(&mut self, pos: IVec2, fill: FloodFill) {
+        let size = self.size().as_ivec2();
+        if pos.cmplt(IVec2::ZERO).any() || pos.cmpge(size).any() {
+            return;
+        }
+
+        let target = self.at(pos);
+        let mut filled = vec![false; (size.x * size.y) as usize];
+        let index = |pos: IVec2| (pos.y * size.x + pos.x) as usize;
+        // diagonal neighbours are reached by looking one pixel past each span
+        let reach = match fill.connectivity {
+            Connectivity::Four => 0,
+            Connectivity::Eight => 1,
+        };
+
+        let mut seeds = vec![pos];
+        while let Some(seed) = seeds.pop() {
+            if filled[index(seed)] || !fill.matches(target, self.at(seed)) {
+                continue;
+            }
+
+            let mut left = seed.x;
+            while left > 0
+                && !filled[index(IVec2::new(left - 1, seed.y))]
+                && fill.matches(target, self.at(IVec2::new(left - 1, seed.y)))
+            {
+                left -= 1;
+            }
+            let mut right = seed.x;
+            while right + 1 < size.x
+                && !filled[index(IVec2::new(right + 1, seed.y))]
+                && fill.matches(target, self.at(IVec2::new(right + 1, seed.y)))
+            {
+                right += 1;
+            }
+
+            for x in left..=right {
+                let pixel = IVec2::new(x, seed.y);
+                filled[index(pixel)] = true;
+                self.draw(pixel.as_u16vec2(), Some(fill.color));
+            }
+
+            for y in [seed.y - 1, seed.y + 1] {
+                if y < 0 || y >= size.y {
+                    continue;
+                }
+                let from = (left - reach).max(0);
+                let to = (right + reach).min(size.x - 1);
+                // one seed per run of matching pixels is enough
+                let mut in_run = false;
+                for x in from..=to {
+                    let pixel = IVec2::new(x, y);
+                    let matches = !filled[index(pixel)] && fill.matches(target, self.at(pixel));
+                    if matches && !in_run {
+                        seeds.push(pixel);
+                    }
+                    in_run = matches;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::Color;
+    use glam::{U16Vec2, Vec2};
+
+    use super::*;
+    use crate::style::{FloodFillLike, Rect};
+
+    fn filled(canvas: &SimpleCanvas) -> usize {
+        let size = canvas.size().as_ivec2();
+        (0..size.y)
+            .flat_map(|y| (0..size.x).map(move |x| IVec2::new(x, y)))
+            .filter(|pos| canvas.at(*pos) == Some(Color::Red))
+            .count()
+    }
+
+    #[test]
+    fn fills_inside_outline() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(12, 6));
+        canvas.rect(Vec2::new(1.0, 1.0), Rect::with_size(Vec2::splat(8.0)));
+        canvas.flood_fill(IVec2::new(4, 4), FloodFill::with_color(Color::Red));
+        assert_eq!(filled(&canvas), 36);
+        assert_eq!(canvas.at(IVec2::new(10, 10)), None);
+
+        // filling outside covers everything that is not the outline
+        canvas.flood_fill(IVec2::new(10, 10), FloodFill::with_color(Color::Red));
+        assert_eq!(filled(&canvas), 144 - 28);
+    }
+
+    #[test]
+    fn connectivity() {
+        let diagonal = || {
+            let mut canvas = SimpleCanvas::headless(U16Vec2::new(8, 4));
+            for x in 0..8 {
+                canvas.point(IVec2::new(x, 7 - x));
+            }
+            canvas
+        };
+
+        let mut four = diagonal();
+        four.flood_fill(IVec2::ZERO, FloodFill::with_color(Color::Red));
+        assert_eq!(filled(&four), 28);
+
+        let mut eight = diagonal();
+        let fill = FloodFill::with_color(Color::Red).connectivity(Connectivity::Eight);
+        eight.flood_fill(IVec2::ZERO, fill);
+        assert_eq!(filled(&eight), 64 - 8);
+    }
+
+    #[test]
+    fn tolerance() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(8, 1));
+        for x in 0..8 {
+            let v = 100 + x as u8 * 4;
+            canvas.point_with_color(IVec2::new(x, 0), Color::Rgb { r: v, g: v, b: v });
+            canvas.point_with_color(IVec2::new(x, 1), Color::Rgb { r: v, g: v, b: v });
+        }
+        canvas.flood_fill(IVec2::ZERO, FloodFill::with_color(Color::Red).tolerance(12));
+        assert_eq!(filled(&canvas), 8);
+        assert_eq!(canvas.at(IVec2::new(3, 0)), Some(Color::Red));
+        assert_ne!(canvas.at(IVec2::new(4, 0)), Some(Color::Red));
+    }
+
+    #[test]
+    fn out_of_bounds() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(4, 2));
+        canvas.flood_fill(IVec2::new(-1, 0), FloodFill::with_color(Color::Red));
+        canvas.flood_fill(IVec2::new(0, 4), FloodFill::with_color(Color::Red));
+        assert_eq!(filled(&canvas), 0);
+    }
+}
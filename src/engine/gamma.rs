@@ -0,0 +1,84 @@
+//! Conversions between sRGB bytes and linear light, so that blending mixes
+//! amounts of light rather than encoded values.
+
+use std::sync::OnceLock;
+
+use rgb::Rgb;
+
+use crate::color::{
+    linear_to_srgb as linear_to_srgb_exact, srgb_to_linear as srgb_to_linear_exact,
+};
+
+/// How many linear values are encoded ahead of time. Enough that every byte
+/// is reachable, including the dark end where sRGB changes fastest.
+const ENCODE_STEPS: usize = 4096;
+
+fn decode_table() -> &'static [f32; 256] {
+    static TABLE: OnceLock<[f32; 256]> = OnceLock::new();
+    TABLE.get_or_init(|| std::array::from_fn(|value| srgb_to_linear_exact(value as f32 / 255.0)))
+}
+
+fn encode_table() -> &'static [u8] {
+    static TABLE: OnceLock<Vec<u8>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        (0..ENCODE_STEPS)
+            .map(|step| {
+                let value = step as f32 / (ENCODE_STEPS - 1) as f32;
+                (linear_to_srgb_exact(value) * 255.0).round() as u8
+            })
+            .collect()
+    })
+}
+
+pub(super) fn srgb_to_linear(value: u8) -> f32 {
+    decode_table()[value as usize]
+}
+
+pub(super) fn linear_to_srgb(value: f32) -> u8 {
+    let step = (value.clamp(0.0, 1.0) * (ENCODE_STEPS - 1) as f32).round();
+    encode_table()[step as usize]
+}
+
+/// Mixes `amount` of `to` into `from`, in linear light or directly on the
+/// sRGB values.
+pub(super) fn mix(from: Rgb<u8>, to: Rgb<u8>, amount: f32, linear: bool) -> Rgb<u8> {
+    let channel = |from: u8, to: u8| {
+        if linear {
+            let (from, to) = (srgb_to_linear(from), srgb_to_linear(to));
+            linear_to_srgb(from + (to - from) * amount)
+        } else {
+            let (from, to) = (from as f32, to as f32);
+            (from + (to - from) * amount) as u8
+        }
+    };
+    Rgb::new(
+        channel(from.r, to.r),
+        channel(from.g, to.g),
+        channel(from.b, to.b),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for value in 0..=255 {
+            assert_eq!(linear_to_srgb(srgb_to_linear(value)), value);
+        }
+        assert_eq!(linear_to_srgb(-1.0), 0);
+        assert_eq!(linear_to_srgb(2.0), 255);
+    }
+
+    #[test]
+    fn coverage_ramps() {
+        let (black, white) = (Rgb::new(0, 0, 0), Rgb::new(255, 255, 255));
+        let ramp =
+            |linear| [0.0, 0.25, 0.5, 0.75, 1.0].map(|amount| mix(black, white, amount, linear).r);
+        // half covered white on black gives half the light, which is brighter
+        // than half the sRGB value
+        assert_eq!(ramp(true), [0, 137, 188, 225, 255]);
+        assert_eq!(ramp(false), [0, 63, 127, 191, 255]);
+    }
+}
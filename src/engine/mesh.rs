@@ -0,0 +1,79 @@
+use std::collections::BTreeSet;
+
+use glam::{Mat4, Vec3, Vec4Swizzles};
+
+use crate::{
+    camera::{clip_to_screen, PerspectiveCamera},
+    mesh::Mesh,
+    style::{MeshStyle, Shading, Triangle, Vertex},
+};
+
+use super::SimpleCanvas;
+
+impl SimpleCanvas {
+    pub(super) fn draw_mesh(
+        &mut self,
+        mesh: &Mesh,
+        model: Mat4,
+        camera: &PerspectiveCamera,
+        style: &MeshStyle,
+    ) {
+        let size = self.size();
+        let mvp = camera.view_projection(size) * model;
+        let screen: Vec<Option<Vec3>> = mesh
+            .positions
+            .iter()
+            .map(|pos| {
+                let clip = mvp * pos.extend(1.0);
+                clip_to_screen(clip.xyz(), clip.w, size)
+            })
+            .collect();
+
+        let mut edges = BTreeSet::new();
+        for face in &mesh.faces {
+            let Some(points) = face
+                .iter()
+                .map(|&i| screen.get(i).copied().flatten())
+                .collect::<Option<Vec<_>>>()
+            else {
+                continue;
+            };
+            let (a, b, c) = (points[0], points[1], points[2]);
+            // flipping y down the canvas flips the sign of the winding
+            let front = (b - a).truncate().perp_dot((c - a).truncate()) < 0.0;
+            if style.cull_backfaces && !front {
+                continue;
+            }
+
+            match style.shading {
+                Shading::Wireframe => {
+                    for (from, to) in [(face[0], face[1]), (face[1], face[2]), (face[2], face[0])] {
+                        edges.insert((from.min(to), from.max(to)));
+                    }
+                }
+                Shading::Flat => {
+                    let [wa, wb, wc] = face.map(|i| model.transform_point3(mesh.positions[i]));
+                    let mut normal = (wb - wa).cross(wc - wa);
+                    if !front {
+                        normal = -normal;
+                    }
+                    let color = style.shade(normal);
+                    let vertices = [a, b, c].map(|point| Vertex {
+                        pos: point.truncate(),
+                        color,
+                        depth: point.z,
+                    });
+                    let mut triangle = Triangle::from_vertices(vertices);
+                    triangle.depth_test = true;
+                    self.draw_triangle(triangle);
+                }
+            }
+        }
+
+        for (from, to) in edges {
+            if let (Some(from), Some(to)) = (screen[from], screen[to]) {
+                self.draw_aa_line(from.truncate(), to.truncate(), Some(style.color));
+            }
+        }
+    }
+}
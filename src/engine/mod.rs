@@ -1,14 +1,49 @@
+mod ascii;
+mod clip;
+mod dither;
+mod ellipse;
+mod fill;
+mod gamma;
+mod mesh;
+mod path;
+mod polygon;
+mod raster;
+mod rect;
 mod renderer;
+mod shape;
+mod stroke;
+mod text;
+mod transform;
+mod triangle;
 
 use crossterm::style::Color;
-use glam::{IVec2, U16Vec2, Vec2};
+use glam::{IVec2, Mat4, U16Vec2, Vec2};
 use renderer::Renderer;
 use rgb::Rgb;
+use transform::Transforms;
 
-use crate::style::{Circle, StyledPrint};
+use crate::{
+    camera::PerspectiveCamera,
+    clip::Clip,
+    mesh::Mesh,
+    palette::{ColorMode, Dither, Palette},
+    style::{
+        Arc, Ascii, BlendMode, Circle, Ellipse, FloodFill, MeshStyle, Path, Polygon, Rect,
+        RectLike, Stroke, StrokeText, StyledPrint, Triangle,
+    },
+};
 
 pub struct SimpleCanvas {
     renderer: Renderer,
+    transforms: Transforms,
+    clips: Vec<Clip>,
+    depth: Vec<f32>,
+    palette: Palette,
+    blend_mode: BlendMode,
+    linear_blending: bool,
+    color_mode: ColorMode,
+    dither: Dither,
+    ascii: Option<Ascii>,
 }
 
 impl SimpleCanvas {
@@ -25,11 +60,64 @@ impl SimpleCanvas {
         self.renderer.set_background_color(color)
     }
 
+    pub fn pixel_mouse(&self) -> bool {
+        self.renderer.pixel_mouse()
+    }
+
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.palette = palette;
+    }
+
+    pub fn palette(&self) -> &Palette {
+        &self.palette
+    }
+
+    pub fn set_blend_mode(&mut self, mode: BlendMode) {
+        self.blend_mode = mode;
+    }
+
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    pub fn set_linear_blending(&mut self, linear: bool) {
+        self.linear_blending = linear;
+    }
+
+    pub fn linear_blending(&self) -> bool {
+        self.linear_blending
+    }
+
+    pub fn set_color_mode(&mut self, mode: ColorMode) {
+        self.color_mode = mode;
+    }
+
+    pub fn color_mode(&self) -> ColorMode {
+        self.color_mode
+    }
+
+    pub fn set_dither(&mut self, dither: Dither) {
+        self.dither = dither;
+    }
+
+    pub fn dither(&self) -> Dither {
+        self.dither
+    }
+
+    pub fn set_ascii(&mut self, ascii: Option<Ascii>) {
+        self.ascii = ascii;
+    }
+
+    pub fn ascii(&self) -> Option<&Ascii> {
+        self.ascii.as_ref()
+    }
+
     pub fn point(&mut self, pos: IVec2) {
         self.point_with_color(pos, Color::White);
     }
 
     pub fn point_with_color(&mut self, pos: IVec2, color: Color) {
+        let pos = self.transform_pixel(pos);
         if pos.x < 0 || pos.y < 0 {
             return;
         }
@@ -41,6 +129,7 @@ impl SimpleCanvas {
     }
 
     pub fn line_with_color(&mut self, start: IVec2, end: IVec2, color: Color) {
+        let (start, end) = (self.transform_pixel(start), self.transform_pixel(end));
         self.draw_line(start, end, Some(color));
     }
 
@@ -48,6 +137,69 @@ impl SimpleCanvas {
         self.draw_aa_circle(pos, circle);
     }
 
+    pub fn ellipse(&mut self, pos: Vec2, ellipse: Ellipse) {
+        self.draw_ellipse(pos, ellipse);
+    }
+
+    pub fn arc(&mut self, pos: Vec2, arc: Arc) {
+        self.draw_arc(pos, arc);
+    }
+
+    pub fn rect(&mut self, pos: Vec2, rect: Rect) {
+        self.draw_rect(pos, rect);
+    }
+
+    pub fn fill_rect(&mut self, pos: Vec2, rect: Rect) {
+        self.draw_filled_rect(pos, rect);
+    }
+
+    pub fn rounded_rect(&mut self, pos: Vec2, radius: f32, rect: Rect) {
+        self.draw_rect(pos, rect.corner_radius(radius));
+    }
+
+    pub fn stroke_circle(&mut self, pos: Vec2, radius: f32, stroke: Stroke) {
+        self.draw_stroked_circle(pos, radius, &stroke);
+    }
+
+    pub fn stroke_rect(&mut self, pos: Vec2, size: Vec2, corner_radius: f32, stroke: Stroke) {
+        self.draw_stroked_rect(pos, size, corner_radius, &stroke);
+    }
+
+    pub fn polygon(&mut self, polygon: Polygon) {
+        self.draw_polygon(&polygon);
+    }
+
+    pub fn path(&mut self, path: &Path) {
+        self.draw_path(path);
+    }
+
+    pub fn stroke_line(&mut self, start: Vec2, end: Vec2, stroke: Stroke) {
+        self.draw_polyline(&[start, end], false, &stroke);
+    }
+
+    pub fn polyline(&mut self, points: &[Vec2], stroke: Stroke) {
+        self.draw_polyline(points, false, &stroke);
+    }
+
+    pub fn quad_bezier(&mut self, start: Vec2, control: Vec2, end: Vec2, stroke: Stroke) {
+        self.draw_quad_bezier(start, control, end, &stroke);
+    }
+
+    pub fn cubic_bezier(
+        &mut self,
+        start: Vec2,
+        first: Vec2,
+        second: Vec2,
+        end: Vec2,
+        stroke: Stroke,
+    ) {
+        self.draw_cubic_bezier([start, first, second, end], &stroke);
+    }
+
+    pub fn spline(&mut self, points: &[Vec2], stroke: Stroke) {
+        self.draw_spline(points, &stroke);
+    }
+
     pub fn aa_line(&mut self, start: Vec2, end: Vec2) {
         self.draw_aa_line(start, end, None);
     }
@@ -56,7 +208,26 @@ impl SimpleCanvas {
         self.draw_aa_line(start, end, Some(color));
     }
 
+    pub fn flood_fill(&mut self, pos: IVec2, fill: FloodFill) {
+        self.draw_flood_fill(self.transform_pixel(pos), fill);
+    }
+
+    pub fn triangle(&mut self, triangle: Triangle) {
+        self.draw_triangle(triangle);
+    }
+
+    pub fn mesh(
+        &mut self,
+        mesh: &Mesh,
+        model: Mat4,
+        camera: &PerspectiveCamera,
+        style: &MeshStyle,
+    ) {
+        self.draw_mesh(mesh, model, camera, style);
+    }
+
     pub fn erase(&mut self, pos: IVec2) {
+        let pos = self.transform_pixel(pos);
         if pos.x < 0 || pos.y < 0 {
             return;
         }
@@ -67,6 +238,10 @@ impl SimpleCanvas {
         self.print_styled_content(content.into());
     }
 
+    pub fn stroke_text<'a>(&mut self, pos: Vec2, text: impl Into<StrokeText<'a>>) {
+        self.draw_stroke_text(pos, text.into());
+    }
+
     pub fn at(&self, pos: IVec2) -> Option<Color> {
         if pos.x < 0 || pos.y < 0 {
             return None;
@@ -77,10 +252,579 @@ impl SimpleCanvas {
 
 #[cfg(test)]
 mod test {
+    use std::{
+        collections::BTreeSet,
+        f32::consts::{FRAC_PI_2, PI, TAU},
+    };
+
+    use glam::Vec3;
+
     use super::*;
+    use crate::{
+        config::AppConfig,
+        style::{
+            ArcLike, AsciiLike, CircleLike, Dash, EllipseLike, FillRule, Gradient, GradientLike,
+            MeshStyleLike, PathLike, PolygonLike, PrintStyle, RectLike, StrokeLike, TriangleLike,
+        },
+    };
 
     #[test]
     fn new() {
-        assert!(SimpleCanvas::new().is_ok());
+        assert!(SimpleCanvas::new(&AppConfig::default()).is_ok());
+    }
+
+    #[test]
+    fn polygon() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(20, 10));
+        let triangle = [
+            Vec2::new(2.0, 2.0),
+            Vec2::new(12.0, 2.0),
+            Vec2::new(2.0, 12.0),
+        ];
+        canvas.polygon(Polygon::new(triangle).fill_color(Rgb::new(255, 0, 0)));
+
+        let red = Some(Color::Rgb { r: 255, g: 0, b: 0 });
+        assert_eq!(canvas.at(IVec2::new(4, 4)), red);
+        assert_eq!(canvas.at(IVec2::new(11, 11)), None);
+        // the hypotenuse passes through the middle of this pixel
+        assert_eq!(
+            canvas.at(IVec2::new(7, 7)),
+            Some(Color::Rgb { r: 188, g: 0, b: 0 })
+        );
+    }
+
+    #[test]
+    fn rect() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(20, 10));
+        canvas.rect(Vec2::new(2.0, 2.0), Rect::square(6.0));
+
+        let white = Some(Color::Rgb {
+            r: 255,
+            g: 255,
+            b: 255,
+        });
+        assert_eq!(canvas.at(IVec2::new(2, 2)), white);
+        assert_eq!(canvas.at(IVec2::new(7, 5)), white);
+        assert_eq!(canvas.at(IVec2::new(4, 4)), None);
+        assert_eq!(canvas.at(IVec2::new(1, 2)), None);
+        assert_eq!(canvas.at(IVec2::new(8, 2)), None);
+
+        canvas.fill_rect(Vec2::new(10.5, 2.0), Rect::square(2.0));
+        assert_eq!(
+            canvas.at(IVec2::new(10, 2)),
+            Some(Color::Rgb {
+                r: 188,
+                g: 188,
+                b: 188
+            })
+        );
+        assert_eq!(canvas.at(IVec2::new(11, 3)), white);
+
+        // a negative stroke width draws nothing rather than spilling outside
+        canvas.rect(Vec2::new(2.0, 12.0), Rect::square(4.0).stroke(-2.0));
+        assert_eq!(canvas.at(IVec2::new(1, 12)), None);
+        assert_eq!(canvas.at(IVec2::new(2, 12)), None);
+    }
+
+    #[test]
+    fn ellipse() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(40, 20));
+        canvas.ellipse(
+            Vec2::new(20.0, 20.0),
+            Ellipse::with_radii(Vec2::new(15.0, 5.0)).solid(),
+        );
+
+        let white = Some(Color::Rgb {
+            r: 255,
+            g: 255,
+            b: 255,
+        });
+        assert_eq!(canvas.at(IVec2::new(8, 20)), white);
+        assert_eq!(canvas.at(IVec2::new(20, 17)), white);
+        assert_eq!(canvas.at(IVec2::new(20, 12)), None);
+    }
+
+    #[test]
+    fn arc() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(40, 20));
+        let center = Vec2::new(20.0, 20.0);
+        let white = Some(Color::Rgb {
+            r: 255,
+            g: 255,
+            b: 255,
+        });
+
+        // a quarter pie in the bottom right
+        canvas.arc(center, Arc::new(10.0, 0.0, FRAC_PI_2).pie());
+        assert_eq!(canvas.at(IVec2::new(23, 23)), white);
+        assert_eq!(canvas.at(IVec2::new(17, 23)), None);
+        assert_eq!(canvas.at(IVec2::new(23, 17)), None);
+
+        // a half donut along the top
+        canvas.arc(center, Arc::new(10.0, PI, TAU).donut(5.0));
+        assert_eq!(canvas.at(IVec2::new(20, 12)), white);
+        assert_eq!(canvas.at(IVec2::new(20, 17)), None);
+    }
+
+    #[test]
+    fn polyline() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(20, 10));
+        let points = [
+            Vec2::new(2.0, 2.0),
+            Vec2::new(12.0, 2.0),
+            Vec2::new(12.0, 12.0),
+        ];
+        canvas.polyline(&points, Stroke::with_width(3.0));
+
+        let white = Some(Color::Rgb {
+            r: 255,
+            g: 255,
+            b: 255,
+        });
+        assert_eq!(canvas.at(IVec2::new(7, 1)), white);
+        assert_eq!(canvas.at(IVec2::new(12, 7)), white);
+        assert_eq!(canvas.at(IVec2::new(7, 7)), None);
+    }
+
+    #[test]
+    fn dashed_rect() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(20, 20));
+        let stroke = Stroke::with_width(2.0).dash(Dash::new([4.0, 4.0]));
+        canvas.stroke_rect(Vec2::new(2.5, 2.5), Vec2::splat(12.0), 0.0, stroke.clone());
+
+        let white = Some(Color::Rgb {
+            r: 255,
+            g: 255,
+            b: 255,
+        });
+        // the top edge runs from x = 2 to 14, starting with a dash
+        assert_eq!(canvas.at(IVec2::new(4, 2)), white);
+        assert_eq!(canvas.at(IVec2::new(8, 2)), None);
+        assert_eq!(canvas.at(IVec2::new(12, 2)), white);
+        assert_eq!(canvas.at(IVec2::new(8, 8)), None);
+
+        // rounded corners are cut off, and the edges stay dashed
+        let mut rounded = SimpleCanvas::headless(U16Vec2::new(20, 20));
+        rounded.stroke_rect(Vec2::new(2.5, 2.5), Vec2::splat(12.0), 4.0, stroke);
+        assert_eq!(rounded.at(IVec2::new(1, 1)), None);
+        assert_eq!(rounded.at(IVec2::new(2, 7)), None);
+        assert_eq!(rounded.at(IVec2::new(2, 10)), white);
+    }
+
+    #[test]
+    fn path() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(20, 20));
+        // a square with a round hole
+        let ring = Path::parse("M2 2 H16 V16 H2 Z M9 5 A4 4 0 1 0 9 13 A4 4 0 1 0 9 5 Z")
+            .unwrap()
+            .fill_rule(FillRule::EvenOdd);
+        canvas.path(&ring);
+
+        let white = Some(Color::Rgb {
+            r: 255,
+            g: 255,
+            b: 255,
+        });
+        assert_eq!(canvas.at(IVec2::new(4, 4)), white);
+        assert_eq!(canvas.at(IVec2::new(9, 9)), None);
+        assert_eq!(canvas.at(IVec2::new(18, 9)), None);
+    }
+
+    #[test]
+    fn gradient_rect() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(10, 2));
+        let gradient = Gradient::linear(Vec2::ZERO, Vec2::new(8.0, 0.0))
+            .stop(0.0, Rgb::new(0, 0, 0))
+            .stop(1.0, Rgb::new(0, 0, 200));
+        let rect = Rect::with_size(Vec2::new(10.0, 4.0)).stroke_color(gradient);
+        canvas.fill_rect(Vec2::ZERO, rect);
+
+        let blue = |b| Some(Color::Rgb { r: 0, g: 0, b });
+        assert_eq!(canvas.at(IVec2::new(0, 0)), blue(0));
+        assert_eq!(canvas.at(IVec2::new(2, 3)), blue(50));
+        assert_eq!(canvas.at(IVec2::new(4, 1)), blue(100));
+        assert_eq!(canvas.at(IVec2::new(9, 0)), blue(200));
+    }
+
+    #[test]
+    fn aa_circle() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(30, 15));
+        let grey = |value| {
+            Some(Color::Rgb {
+                r: value,
+                g: value,
+                b: value,
+            })
+        };
+
+        canvas.aa_circle(Vec2::new(10.0, 10.0), Circle::with_radius(5.0).solid());
+        assert_eq!(canvas.at(IVec2::new(10, 10)), grey(255));
+        assert_eq!(canvas.at(IVec2::new(14, 10)), grey(255));
+        // the edge passes through the middle of this pixel
+        assert_eq!(canvas.at(IVec2::new(15, 10)), grey(188));
+        assert_eq!(canvas.at(IVec2::new(16, 10)), None);
+
+        // a ring one pixel wide centred on whole pixels
+        canvas.aa_circle(Vec2::new(22.0, 10.0), Circle::with_radius(5.0).stroke(1.0));
+        assert_eq!(canvas.at(IVec2::new(27, 10)), grey(255));
+        assert_eq!(canvas.at(IVec2::new(22, 5)), grey(255));
+        assert_eq!(canvas.at(IVec2::new(26, 10)), None);
+        assert_eq!(canvas.at(IVec2::new(22, 10)), None);
+    }
+
+    #[test]
+    fn blends_with_named_colors() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(30, 15));
+        canvas.set_background_color(Some(Color::Blue));
+        let circle = Circle::with_radius(5.0).solid();
+        canvas.aa_circle(Vec2::new(10.0, 10.0), circle.clone());
+        // half covered, over the default bright blue of (92, 92, 255)
+        assert_eq!(
+            canvas.at(IVec2::new(15, 10)),
+            Some(Color::Rgb {
+                r: 196,
+                g: 196,
+                b: 255
+            })
+        );
+
+        canvas.set_palette(Palette::vga());
+        canvas.point_with_color(IVec2::new(15, 10), Color::AnsiValue(1));
+        canvas.aa_circle(Vec2::new(10.0, 10.0), circle);
+        assert_eq!(
+            canvas.at(IVec2::new(15, 10)),
+            Some(Color::Rgb {
+                r: 218,
+                g: 188,
+                b: 188
+            })
+        );
+    }
+
+    #[test]
+    fn linear_blending() {
+        // the edge of each rect covers a quarter more of its pixel than the last
+        let ramp = |linear| {
+            let mut canvas = SimpleCanvas::headless(U16Vec2::new(20, 10));
+            canvas.set_linear_blending(linear);
+            [0.25, 0.5, 0.75].map(|coverage| {
+                let y = coverage * 16.0;
+                canvas.fill_rect(Vec2::new(11.0 - coverage, y), Rect::square(2.0));
+                match canvas.at(IVec2::new(10, y as i32 + 1)) {
+                    Some(Color::Rgb { r, .. }) => r,
+                    color => panic!("expected an RGB color, got {color:?}"),
+                }
+            })
+        };
+        assert_eq!(ramp(true), [137, 188, 225]);
+        assert_eq!(ramp(false), [63, 127, 191]);
+    }
+
+    #[test]
+    fn dithering() {
+        let grey = Color::Rgb {
+            r: 160,
+            g: 160,
+            b: 160,
+        };
+        let indices = |dither| {
+            let mut canvas = SimpleCanvas::headless(U16Vec2::new(8, 4));
+            canvas.set_color_mode(ColorMode::Ansi16);
+            canvas.set_dither(dither);
+            canvas.set_background_color(Some(grey));
+            canvas.fill_rect(
+                Vec2::ZERO,
+                Rect::square(8.0).stroke_color(Rgb::new(160, 160, 160)),
+            );
+            canvas.reduce_colors();
+            let mut indices = BTreeSet::new();
+            for y in 0..8 {
+                for x in 0..8 {
+                    match canvas.at(IVec2::new(x, y)) {
+                        Some(Color::AnsiValue(index)) => indices.insert(index),
+                        color => panic!("expected a palette color, got {color:?}"),
+                    };
+                }
+            }
+            assert!(canvas.renderer.cells_mut().iter().all(|cell| matches!(
+                cell.style.background_color,
+                None | Some(Color::AnsiValue(_))
+            )));
+            indices
+        };
+        assert_eq!(indices(Dither::None), BTreeSet::from([8]));
+        assert_eq!(indices(Dither::FloydSteinberg), BTreeSet::from([7, 8]));
+        assert_eq!(indices(Dither::Atkinson), BTreeSet::from([7, 8]));
+        assert_eq!(indices(Dither::Bayer), BTreeSet::from([7, 8]));
+    }
+
+    #[test]
+    fn reduced_text_colors() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(8, 4));
+        canvas.set_color_mode(ColorMode::Ansi256);
+        canvas.set_dither(Dither::FloydSteinberg);
+        let style = PrintStyle {
+            foreground_color: Some(Color::Rgb {
+                r: 250,
+                g: 130,
+                b: 10,
+            }),
+            ..Default::default()
+        };
+        canvas.print(style.apply("hi"));
+        canvas.reduce_colors();
+        let cell = canvas
+            .renderer
+            .cells_mut()
+            .iter()
+            .find(|cell| cell.c == 'h')
+            .unwrap();
+        assert_eq!(cell.style.foreground_color, Some(Color::AnsiValue(208)));
+    }
+
+    #[test]
+    fn ascii() {
+        let cell = |canvas: &mut SimpleCanvas, x: usize, y: usize| {
+            let width = canvas.renderer.size().x as usize;
+            canvas.renderer.cells_mut()[y * width + x].clone()
+        };
+        let draw = |ascii: Ascii| {
+            let mut canvas = SimpleCanvas::headless(U16Vec2::new(10, 4));
+            canvas.fill_rect(Vec2::ZERO, Rect::with_size(Vec2::new(5.0, 8.0)));
+            canvas.point_with_color(IVec2::new(8, 6), Color::White);
+            canvas.print("hi");
+            canvas.encode_ascii(&ascii);
+            canvas
+        };
+
+        let mut canvas = draw(Ascii::new());
+        let white = Some(Color::Rgb {
+            r: 255,
+            g: 255,
+            b: 255,
+        });
+        assert_eq!(cell(&mut canvas, 2, 1).c, '@');
+        assert_eq!(cell(&mut canvas, 2, 1).style.foreground_color, white);
+        assert_eq!(cell(&mut canvas, 4, 1).c, '@');
+        assert_eq!(cell(&mut canvas, 7, 1).c, ' ');
+        // half of the cell is lit
+        assert_eq!(cell(&mut canvas, 8, 3).c, '#');
+        // text is left alone
+        assert!(canvas.renderer.cells_mut().iter().any(|cell| cell.c == 'h'));
+
+        let mut canvas = draw(Ascii::new().colored(false).edges(0.3));
+        assert_eq!(cell(&mut canvas, 2, 1).c, '@');
+        assert_eq!(cell(&mut canvas, 2, 1).style.foreground_color, None);
+        assert_eq!(cell(&mut canvas, 4, 1).c, '|');
+        assert_eq!(cell(&mut canvas, 5, 1).c, '|');
+
+        // spaces in text keep their background
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(10, 4));
+        canvas.set_ascii(Some(Ascii::new()));
+        let blue = Some(Color::Rgb { r: 0, g: 0, b: 255 });
+        let style = PrintStyle {
+            background_color: blue,
+            ..Default::default()
+        };
+        canvas.print(style.apply("a b"));
+        canvas.encode_ascii(&Ascii::new());
+        let cells = canvas.renderer.cells_mut();
+        let start = cells.iter().position(|cell| cell.c == 'a').unwrap();
+        for (cell, c) in cells[start..].iter().zip("a b".chars()) {
+            assert_eq!(cell.c, c);
+            assert_eq!(cell.style.background_color, blue);
+        }
+        assert_eq!(cells[start + 3].style.background_color, None);
+    }
+
+    #[test]
+    fn blend_modes() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(30, 15));
+        let grey = Color::Rgb {
+            r: 100,
+            g: 100,
+            b: 100,
+        };
+        canvas.set_background_color(Some(grey));
+
+        canvas.set_blend_mode(BlendMode::Add);
+        canvas.point_with_color(IVec2::new(1, 1), Color::Rgb { r: 100, g: 0, b: 0 });
+        assert_eq!(
+            canvas.at(IVec2::new(1, 1)),
+            Some(Color::Rgb {
+                r: 200,
+                g: 100,
+                b: 100
+            })
+        );
+
+        // edges fade between the canvas and the multiplied color
+        canvas.set_blend_mode(BlendMode::Multiply);
+        let circle = Circle::with_radius(5.0)
+            .solid()
+            .stroke_color(Rgb::new(0, 0, 0));
+        canvas.aa_circle(Vec2::new(10.0, 10.0), circle);
+        assert_eq!(
+            canvas.at(IVec2::new(10, 10)),
+            Some(Color::Rgb { r: 0, g: 0, b: 0 })
+        );
+        assert_eq!(
+            canvas.at(IVec2::new(15, 10)),
+            Some(Color::Rgb {
+                r: 71,
+                g: 71,
+                b: 71
+            })
+        );
+
+        // erasing is not blended
+        canvas.erase(IVec2::new(10, 10));
+        assert_eq!(canvas.at(IVec2::new(10, 10)), None);
+    }
+
+    #[test]
+    fn transformed() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(20, 10));
+        let white = Some(Color::Rgb {
+            r: 255,
+            g: 255,
+            b: 255,
+        });
+
+        canvas.translate(Vec2::new(10.0, 0.0));
+        canvas.scale(Vec2::splat(2.0));
+        canvas.fill_rect(Vec2::ZERO, Rect::square(2.0));
+        assert_eq!(canvas.at(IVec2::new(10, 2)), white);
+        assert_eq!(canvas.at(IVec2::new(12, 2)), white);
+        assert_eq!(canvas.at(IVec2::new(8, 2)), None);
+        assert_eq!(canvas.at(IVec2::new(14, 2)), None);
+
+        // stretching a circle draws an ellipse
+        canvas.reset_transform();
+        canvas.translate(Vec2::new(5.0, 10.0));
+        canvas.scale(Vec2::new(1.0, 2.0));
+        canvas.aa_circle(Vec2::ZERO, Circle::with_radius(3.0).solid());
+        assert_eq!(canvas.at(IVec2::new(5, 15)), white);
+        assert_eq!(canvas.at(IVec2::new(5, 5)), white);
+        assert_eq!(canvas.at(IVec2::new(9, 10)), None);
+    }
+
+    #[test]
+    fn triangle() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(40, 20));
+        let points = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(30.0, 0.0),
+            Vec2::new(0.0, 30.0),
+        ];
+        let colors = [
+            Rgb::new(255, 0, 0),
+            Rgb::new(0, 255, 0),
+            Rgb::new(0, 0, 255),
+        ];
+        canvas.triangle(Triangle::new(points).colors(colors));
+
+        assert_eq!(
+            canvas.at(IVec2::ZERO),
+            Some(Color::Rgb { r: 255, g: 0, b: 0 })
+        );
+        assert_eq!(
+            canvas.at(IVec2::new(3, 6)),
+            Some(Color::Rgb {
+                r: 179,
+                g: 26,
+                b: 51
+            })
+        );
+        assert_eq!(canvas.at(IVec2::new(20, 20)), None);
+    }
+
+    #[test]
+    fn depth_test() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(20, 10));
+        let red = Rgb::new(255, 0, 0);
+        let blue = Rgb::new(0, 0, 255);
+        let square = |canvas: &mut SimpleCanvas, color, depths: [f32; 2]| {
+            let [left, right] = depths;
+            let (a, b, c, d) = (
+                Vec2::new(0.0, 0.0),
+                Vec2::new(20.0, 0.0),
+                Vec2::new(20.0, 20.0),
+                Vec2::new(0.0, 20.0),
+            );
+            let triangles = [
+                Triangle::new([a, b, c]).depths([left, right, right]),
+                Triangle::new([a, c, d]).depths([left, right, left]),
+            ];
+            for triangle in triangles {
+                canvas.triangle(triangle.color(color));
+            }
+        };
+
+        // two planes crossing in the middle of the canvas
+        square(&mut canvas, red, [0.0, 1.0]);
+        square(&mut canvas, blue, [1.0, 0.0]);
+        let red = Some(Color::Rgb { r: 255, g: 0, b: 0 });
+        let blue = Some(Color::Rgb { r: 0, g: 0, b: 255 });
+        assert_eq!(canvas.at(IVec2::new(3, 10)), red);
+        assert_eq!(canvas.at(IVec2::new(16, 10)), blue);
+
+        // without depth testing, the last triangle wins
+        canvas.clear_depth();
+        canvas.triangle(
+            Triangle::new([Vec2::ZERO, Vec2::new(20.0, 0.0), Vec2::new(0.0, 20.0)])
+                .color(Rgb::new(0, 255, 0)),
+        );
+        assert_eq!(
+            canvas.at(IVec2::new(3, 10)),
+            Some(Color::Rgb { r: 0, g: 255, b: 0 })
+        );
+    }
+
+    #[test]
+    fn mesh() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(20, 10));
+        let camera = PerspectiveCamera::default();
+        let cube = Mesh::cube();
+        let style = MeshStyle::flat().light(Vec3::NEG_Z).ambient(0.5);
+
+        // the front of the cube faces the light, the back faces are culled
+        canvas.mesh(&cube, Mat4::IDENTITY, &camera, &style);
+        let white = Some(Color::Rgb {
+            r: 255,
+            g: 255,
+            b: 255,
+        });
+        assert_eq!(canvas.at(IVec2::new(10, 10)), white);
+        assert_eq!(canvas.at(IVec2::ZERO), None);
+
+        // a closer cube is drawn in front, whatever the order
+        let closer =
+            Mat4::from_translation(Vec3::new(0.0, 0.0, 1.0)) * Mat4::from_scale(Vec3::splat(0.5));
+        canvas.mesh(&cube, closer, &camera, &style.color(Rgb::new(255, 0, 0)));
+        canvas.mesh(&cube, Mat4::IDENTITY, &camera, &style);
+        assert_eq!(
+            canvas.at(IVec2::new(10, 10)),
+            Some(Color::Rgb { r: 255, g: 0, b: 0 })
+        );
+
+        // turned away from the camera, a triangle is only drawn without culling
+        let triangle = Mesh::new(vec![Vec3::ZERO, Vec3::Y, Vec3::X], vec![[0, 1, 2]]);
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(20, 10));
+        canvas.mesh(&triangle, Mat4::IDENTITY, &camera, &style);
+        assert!(!canvas.drawn_mask().contains(IVec2::new(11, 8)));
+        canvas.mesh(
+            &triangle,
+            Mat4::IDENTITY,
+            &camera,
+            &style.cull_backfaces(false),
+        );
+        assert_eq!(canvas.at(IVec2::new(11, 8)), white);
+
+        // wireframes only draw edges
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(20, 10));
+        canvas.mesh(&cube, Mat4::IDENTITY, &camera, &MeshStyle::wireframe());
+        assert_eq!(canvas.at(IVec2::new(8, 9)), None);
+        assert!(canvas.drawn_mask().contains(IVec2::new(10, 6)));
     }
 }
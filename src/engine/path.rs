@@ -0,0 +1,161 @@
+use glam::Vec2;
+
+use crate::style::{Path, PathCommand};
+
+use super::{
+    shape::{cubic_points, quad_points, svg_arc_points},
+    SimpleCanvas,
+};
+
+/// A run of connected points from a single move command.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Subpath {
+    pub(crate) points: Vec<Vec2>,
+    pub(crate) closed: bool,
+}
+
+/// Flattens the curves and arcs of a path into polylines.
+pub(crate) fn flatten(path: &Path) -> Vec<Subpath> {
+    let mut subpaths = vec![];
+    let mut current = Subpath {
+        points: vec![],
+        closed: false,
+    };
+    let mut start = Vec2::ZERO;
+
+    for command in &path.commands {
+        let last = current.points.last().copied().unwrap_or(start);
+        match *command {
+            PathCommand::MoveTo(point) => {
+                finish(&mut subpaths, &mut current);
+                start = point;
+                current.points.push(point);
+            }
+            PathCommand::LineTo(point) => {
+                begin(&mut current, last);
+                current.points.push(point);
+            }
+            PathCommand::QuadTo(control, end) => {
+                begin(&mut current, last);
+                current
+                    .points
+                    .extend(quad_points(last, control, end).into_iter().skip(1));
+            }
+            PathCommand::CubicTo(first, second, end) => {
+                begin(&mut current, last);
+                current
+                    .points
+                    .extend(cubic_points(last, first, second, end).into_iter().skip(1));
+            }
+            PathCommand::ArcTo {
+                radii,
+                rotation,
+                large_arc,
+                sweep,
+                end,
+            } => {
+                begin(&mut current, last);
+                let arc = svg_arc_points(last, end, radii, rotation, large_arc, sweep);
+                current.points.extend(arc.into_iter().skip(1));
+            }
+            PathCommand::Close => {
+                if !current.points.is_empty() {
+                    current.closed = true;
+                    finish(&mut subpaths, &mut current);
+                }
+                // drawing after a close carries on from the start point
+                current.points.clear();
+            }
+        }
+    }
+    finish(&mut subpaths, &mut current);
+    subpaths
+}
+
+/// Starts a subpath at `point` if a drawing command follows a close.
+fn begin(current: &mut Subpath, point: Vec2) {
+    if current.points.is_empty() {
+        current.points.push(point);
+    }
+}
+
+fn finish(subpaths: &mut Vec<Subpath>, current: &mut Subpath) {
+    let mut subpath = std::mem::replace(
+        current,
+        Subpath {
+            points: vec![],
+            closed: false,
+        },
+    );
+    // the closing segment is implied, so drop a duplicate end point
+    if subpath.closed && subpath.points.len() > 1 && subpath.points.first() == subpath.points.last()
+    {
+        subpath.points.pop();
+    }
+    if subpath.points.len() > 1 || subpath.closed {
+        subpaths.push(subpath);
+    }
+}
+
+impl SimpleCanvas {
+    pub(super) fn draw_path(&mut self, path: &Path) {
+        let subpaths = flatten(path);
+        if let Some(paint) = path.fill() {
+            let contours: Vec<Vec<Vec2>> = subpaths.iter().map(|s| s.points.clone()).collect();
+            self.paint_contours(&contours, path.fill_rule, &paint);
+        }
+        if let Some(stroke) = &path.stroke {
+            for subpath in &subpaths {
+                self.draw_polyline(&subpath.points, subpath.closed, stroke);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subpaths() {
+        let path = Path::parse("M0 0 L4 0 L4 4 Z M10 10 L12 10").unwrap();
+        let subpaths = flatten(&path);
+        assert_eq!(
+            subpaths,
+            vec![
+                Subpath {
+                    points: vec![Vec2::ZERO, Vec2::new(4.0, 0.0), Vec2::new(4.0, 4.0)],
+                    closed: true,
+                },
+                Subpath {
+                    points: vec![Vec2::new(10.0, 10.0), Vec2::new(12.0, 10.0)],
+                    closed: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn drawing_after_close() {
+        let path = Path::parse("M1 1 L5 1 L5 5 Z L1 5").unwrap();
+        let subpaths = flatten(&path);
+        assert_eq!(subpaths.len(), 2);
+        assert_eq!(subpaths[1].points, vec![Vec2::ONE, Vec2::new(1.0, 5.0)]);
+    }
+
+    #[test]
+    fn curves_are_flattened() {
+        let path = Path::parse("M0 0 Q10 10 20 0 A10 10 0 0 1 0 0").unwrap();
+        let subpaths = flatten(&path);
+        assert_eq!(subpaths.len(), 1);
+        let points = &subpaths[0].points;
+        assert!(points.len() > 6);
+        assert!(points.contains(&Vec2::new(20.0, 0.0)));
+        assert_eq!(points[points.len() - 1], Vec2::ZERO);
+    }
+
+    #[test]
+    fn lone_move() {
+        assert!(flatten(&Path::parse("M1 1 M2 2").unwrap()).is_empty());
+    }
+}
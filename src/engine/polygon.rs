@@ -0,0 +1,48 @@
+use std::slice;
+
+use glam::Vec2;
+use rgb::Rgb;
+
+use crate::style::{FillRule, Polygon, Stroke};
+
+use super::{
+    shape::{cubic_points, quad_points, spline_points},
+    stroke::stroke_outline,
+    SimpleCanvas,
+};
+
+impl SimpleCanvas {
+    pub(super) fn draw_polygon(&mut self, polygon: &Polygon) {
+        if let Some(paint) = polygon.fill() {
+            self.paint_contours(slice::from_ref(&polygon.points), polygon.fill_rule, &paint);
+        }
+        if let Some(stroke) = &polygon.stroke {
+            self.draw_polyline(&polygon.points, true, stroke);
+        }
+    }
+
+    pub(super) fn draw_polyline(&mut self, points: &[Vec2], closed: bool, stroke: &Stroke) {
+        let color = stroke.color.unwrap_or(Rgb::new(255, 255, 255));
+        let outline = stroke_outline(points, closed, stroke);
+        self.fill_contours(&outline, FillRule::NonZero, color);
+    }
+
+    pub(super) fn draw_quad_bezier(
+        &mut self,
+        start: Vec2,
+        control: Vec2,
+        end: Vec2,
+        stroke: &Stroke,
+    ) {
+        self.draw_polyline(&quad_points(start, control, end), false, stroke);
+    }
+
+    pub(super) fn draw_cubic_bezier(&mut self, curve: [Vec2; 4], stroke: &Stroke) {
+        let [start, first, second, end] = curve;
+        self.draw_polyline(&cubic_points(start, first, second, end), false, stroke);
+    }
+
+    pub(super) fn draw_spline(&mut self, points: &[Vec2], stroke: &Stroke) {
+        self.draw_polyline(&spline_points(points), false, stroke);
+    }
+}
@@ -0,0 +1,252 @@
+use glam::{IVec2, UVec2, Vec2};
+
+use crate::style::FillRule;
+
+/// The number of sample rows taken per pixel row.
+const SUB_SCANLINES: usize = 16;
+
+/// Per-pixel area coverage of a filled shape.
+///
+/// Pixel centres sit on integer coordinates, the same as the rest of the
+/// canvas, so the pixel at `(x, y)` covers `x - 0.5..x + 0.5`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Coverage {
+    origin: IVec2,
+    size: UVec2,
+    cells: Vec<f32>,
+}
+
+impl Coverage {
+    /// Iterates over every pixel that is at least partially covered.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (IVec2, f32)> + '_ {
+        let width = self.size.x.max(1) as usize;
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, coverage)| **coverage > f32::EPSILON)
+            .map(move |(i, coverage)| {
+                let offset = IVec2::new((i % width) as i32, (i / width) as i32);
+                (self.origin + offset, coverage.min(1.0))
+            })
+    }
+
+    /// Returns the coverage of a single pixel.
+    #[cfg(test)]
+    pub(crate) fn at(&self, pos: IVec2) -> f32 {
+        let local = pos - self.origin;
+        if local.x < 0
+            || local.y < 0
+            || local.x >= self.size.x as i32
+            || local.y >= self.size.y as i32
+        {
+            return 0.0;
+        }
+        self.cells[(local.y as u32 * self.size.x + local.x as u32) as usize].min(1.0)
+    }
+}
+
+/// Computes the coverage of a set of closed contours, limited to the pixels
+/// in `min..max`.
+///
+/// Coverage is exact horizontally and sampled with [`SUB_SCANLINES`] rows
+/// vertically.
+pub(crate) fn rasterize(
+    contours: &[Vec<Vec2>],
+    rule: FillRule,
+    min: IVec2,
+    max: IVec2,
+) -> Coverage {
+    // shift so that pixel `x` spans `x..x + 1`
+    let edges: Vec<(Vec2, Vec2)> = contours
+        .iter()
+        .filter(|contour| contour.len() > 2)
+        .flat_map(|contour| {
+            contour
+                .iter()
+                .zip(contour.iter().cycle().skip(1))
+                .map(|(a, b)| (*a + Vec2::splat(0.5), *b + Vec2::splat(0.5)))
+        })
+        .filter(|(a, b)| a.y != b.y)
+        .collect();
+
+    let (lower, upper) = edges.iter().fold(
+        (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+        |(lower, upper), (a, b)| (lower.min(a.min(*b)), upper.max(a.max(*b))),
+    );
+    if edges.is_empty() || !lower.is_finite() || !upper.is_finite() {
+        return Coverage {
+            origin: min,
+            size: UVec2::ZERO,
+            cells: vec![],
+        };
+    }
+
+    let origin = lower.floor().as_ivec2().max(min);
+    let end = upper.ceil().as_ivec2().min(max);
+    let size = (end - origin).max(IVec2::ZERO).as_uvec2();
+    let mut cells = vec![0.0; (size.x * size.y) as usize];
+
+    let sample_weight = 1.0 / SUB_SCANLINES as f32;
+    let mut crossings: Vec<(f32, i32)> = Vec::with_capacity(edges.len());
+
+    for row in 0..size.y {
+        let y = (origin.y + row as i32) as f32;
+        let row_cells = &mut cells[(row * size.x) as usize..((row + 1) * size.x) as usize];
+
+        for sample in 0..SUB_SCANLINES {
+            let sample_y = y + (sample as f32 + 0.5) * sample_weight;
+
+            crossings.clear();
+            for (a, b) in edges.iter() {
+                let (top, bottom, winding) = if a.y < b.y { (a, b, 1) } else { (b, a, -1) };
+                if sample_y < top.y || sample_y >= bottom.y {
+                    continue;
+                }
+                let t = (sample_y - top.y) / (bottom.y - top.y);
+                crossings.push((top.x + (bottom.x - top.x) * t, winding));
+            }
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut winding = 0;
+            for pair in crossings.windows(2) {
+                winding += pair[0].1;
+                let inside = match rule {
+                    FillRule::NonZero => winding != 0,
+                    FillRule::EvenOdd => winding % 2 != 0,
+                };
+                if inside {
+                    let start = pair[0].0 - origin.x as f32;
+                    let end = pair[1].0 - origin.x as f32;
+                    add_span(row_cells, start, end, sample_weight);
+                }
+            }
+        }
+    }
+
+    Coverage {
+        origin,
+        size,
+        cells,
+    }
+}
+
+/// Adds the horizontal overlap of `start..end` with every pixel in the row.
+fn add_span(row: &mut [f32], start: f32, end: f32, weight: f32) {
+    let start = start.max(0.0);
+    let end = end.min(row.len() as f32);
+    if start >= end {
+        return;
+    }
+
+    let first = start.floor() as usize;
+    let last = (end.ceil() as usize).min(row.len());
+    for (x, cell) in row.iter_mut().enumerate().take(last).skip(first) {
+        let overlap = end.min(x as f32 + 1.0) - start.max(x as f32);
+        if overlap > 0.0 {
+            *cell += overlap * weight;
+        }
+    }
+}
+
+/// Returns the contour wound so that its signed area is positive, so that
+/// overlapping pieces add up instead of cancelling under
+/// [`FillRule::NonZero`].
+pub(crate) fn oriented(mut contour: Vec<Vec2>) -> Vec<Vec2> {
+    if signed_area(&contour) < 0.0 {
+        contour.reverse();
+    }
+    contour
+}
+
+/// The signed area of a closed contour.
+pub(crate) fn signed_area(contour: &[Vec2]) -> f32 {
+    contour
+        .iter()
+        .zip(contour.iter().cycle().skip(1))
+        .map(|(a, b)| a.perp_dot(*b))
+        .sum::<f32>()
+        / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOUNDS: (IVec2, IVec2) = (IVec2::ZERO, IVec2::splat(100));
+
+    fn square(min: Vec2, max: Vec2) -> Vec<Vec2> {
+        vec![min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)]
+    }
+
+    fn total(coverage: &Coverage) -> f32 {
+        coverage.iter().map(|(_, c)| c).sum()
+    }
+
+    #[test]
+    fn pixel_aligned_square() {
+        let contour = square(Vec2::splat(1.5), Vec2::splat(4.5));
+        let coverage = rasterize(&[contour], FillRule::NonZero, BOUNDS.0, BOUNDS.1);
+        assert_eq!(coverage.at(IVec2::new(2, 2)), 1.0);
+        assert_eq!(coverage.at(IVec2::new(4, 4)), 1.0);
+        assert_eq!(coverage.at(IVec2::new(1, 2)), 0.0);
+        assert_eq!(coverage.at(IVec2::new(5, 2)), 0.0);
+        assert!((total(&coverage) - 9.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn partial_coverage() {
+        let contour = square(Vec2::new(1.5, 1.5), Vec2::new(3.0, 3.0));
+        let coverage = rasterize(&[contour], FillRule::NonZero, BOUNDS.0, BOUNDS.1);
+        assert_eq!(coverage.at(IVec2::new(2, 2)), 1.0);
+        assert!((coverage.at(IVec2::new(3, 2)) - 0.5).abs() < 1e-4);
+        assert!((coverage.at(IVec2::new(3, 3)) - 0.25).abs() < 1e-4);
+        assert!((total(&coverage) - 2.25).abs() < 1e-4);
+    }
+
+    #[test]
+    fn fill_rules() {
+        let outer = square(Vec2::splat(0.5), Vec2::splat(6.5));
+        let inner = square(Vec2::splat(2.5), Vec2::splat(4.5));
+        let contours = [outer, inner];
+
+        let non_zero = rasterize(&contours, FillRule::NonZero, BOUNDS.0, BOUNDS.1);
+        assert_eq!(non_zero.at(IVec2::new(3, 3)), 1.0);
+
+        let even_odd = rasterize(&contours, FillRule::EvenOdd, BOUNDS.0, BOUNDS.1);
+        assert_eq!(even_odd.at(IVec2::new(3, 3)), 0.0);
+        assert_eq!(even_odd.at(IVec2::new(1, 1)), 1.0);
+    }
+
+    #[test]
+    fn self_intersecting() {
+        // a pentagram drawn in one stroke has a centre that winds twice
+        let star: Vec<Vec2> = (0..5)
+            .map(|i| {
+                let angle = i as f32 * 4.0 * std::f32::consts::PI / 5.0;
+                Vec2::new(20.0, 20.0) + Vec2::from_angle(angle) * 15.0
+            })
+            .collect();
+        let centre = IVec2::new(20, 20);
+        let contours = [star];
+        let non_zero = rasterize(&contours, FillRule::NonZero, BOUNDS.0, BOUNDS.1);
+        let even_odd = rasterize(&contours, FillRule::EvenOdd, BOUNDS.0, BOUNDS.1);
+        assert_eq!(non_zero.at(centre), 1.0);
+        assert_eq!(even_odd.at(centre), 0.0);
+    }
+
+    #[test]
+    fn clipped_to_bounds() {
+        let contour = square(Vec2::splat(-50.0), Vec2::splat(500.0));
+        let coverage = rasterize(&[contour], FillRule::NonZero, IVec2::ZERO, IVec2::splat(4));
+        assert_eq!(coverage.iter().count(), 16);
+    }
+
+    #[test]
+    fn orientation() {
+        let contour = square(Vec2::ZERO, Vec2::ONE);
+        assert_eq!(signed_area(&contour), 1.0);
+        let reversed: Vec<Vec2> = contour.iter().rev().copied().collect();
+        assert_eq!(signed_area(&reversed), -1.0);
+        assert_eq!(oriented(reversed), contour);
+    }
+}
@@ -0,0 +1,63 @@
+use glam::Vec2;
+
+use crate::style::{FillRule, Paint, Rect, Stroke};
+
+use super::{shape::rounded_rect_contour, SimpleCanvas};
+
+impl Rect {
+    fn paint(&self) -> Paint {
+        self.stroke_color.clone().unwrap_or_default()
+    }
+
+    /// The corners in canvas coordinates, where pixel centres are on whole
+    /// numbers.
+    fn bounds(&self, pos: Vec2) -> (Vec2, Vec2) {
+        let min = pos - Vec2::splat(0.5);
+        (min, min + self.size)
+    }
+}
+
+impl SimpleCanvas {
+    pub(super) fn draw_rect(&mut self, pos: Vec2, rect: Rect) {
+        if rect.size.min_element() <= 0.0 {
+            return;
+        }
+
+        let (min, max) = rect.bounds(pos);
+        let stroke = rect.stroke_width.unwrap_or(1.0).max(0.0);
+        let mut contours = vec![rounded_rect_contour(min, max, rect.corner_radius)];
+        if stroke * 2.0 < rect.size.min_element() {
+            let inset = Vec2::splat(stroke);
+            let radius = (rect.corner_radius - stroke).max(0.0);
+            contours.push(rounded_rect_contour(min + inset, max - inset, radius));
+        }
+        self.paint_contours(&contours, FillRule::EvenOdd, &rect.paint());
+    }
+
+    pub(super) fn draw_filled_rect(&mut self, pos: Vec2, rect: Rect) {
+        if rect.size.min_element() <= 0.0 {
+            return;
+        }
+
+        let (min, max) = rect.bounds(pos);
+        let contour = rounded_rect_contour(min, max, rect.corner_radius);
+        self.paint_contours(&[contour], FillRule::NonZero, &rect.paint());
+    }
+
+    /// Strokes the rect outline with the stroke centred on the edges.
+    pub(super) fn draw_stroked_rect(
+        &mut self,
+        pos: Vec2,
+        size: Vec2,
+        corner_radius: f32,
+        stroke: &Stroke,
+    ) {
+        if size.min_element() <= 0.0 {
+            return;
+        }
+
+        let (min, max) = Rect::with_size(size).bounds(pos);
+        let outline = rounded_rect_contour(min, max, corner_radius);
+        self.draw_polyline(&outline, true, stroke);
+    }
+}
@@ -1,28 +1,36 @@
 use std::{
+    f32::consts::TAU,
     io::{self, stdout, Stdout, Write},
     mem::swap,
     panic::{set_hook, take_hook},
 };
 
 use crossterm::{
-    cursor, execute,
-    style::{Color, ContentStyle},
+    cursor, event,
+    style::{self, Color, ContentStyle},
     terminal, QueueableCommand,
 };
-use glam::{IVec2, U16Vec2, Vec2};
+use glam::{Affine2, IVec2, U16Vec2, Vec2};
 use line_drawing::{Bresenham, XiaolinWu};
-use rand_distr::num_traits::pow;
 use rgb::Rgb;
 use unicode_width::UnicodeWidthStr;
 
-use crate::style::{CanvasAlignment, Circle, StyledPrint};
+use crate::{
+    config::AppConfig,
+    mouse,
+    palette::{ColorMode, Dither, Palette},
+    style::{BlendMode, CanvasAlignment, Circle, FillRule, Paint, StyledPrint},
+};
 
-use super::SimpleCanvas;
+use super::{gamma::mix, raster::rasterize, shape::arc_points, SimpleCanvas};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct Cell {
     pub(crate) c: char,
     pub(crate) style: ContentStyle,
+    /// Whether the cell was last written by printing text rather than as
+    /// pixels.
+    pub(crate) text: bool,
 }
 
 impl Default for Cell {
@@ -30,6 +38,7 @@ impl Default for Cell {
         Self {
             c: ' ',
             style: ContentStyle::default(),
+            text: false,
         }
     }
 }
@@ -40,7 +49,11 @@ impl Cell {
             background_color: color,
             ..Default::default()
         };
-        Self { c: ' ', style }
+        Self {
+            c: ' ',
+            style,
+            text: false,
+        }
     }
 }
 
@@ -279,6 +292,11 @@ impl DoubleBuffer {
         self.get_mut(idx)
     }
 
+    /// Returns the cells of the frame being drawn.
+    pub(super) fn cells_mut(&mut self) -> &mut [Cell] {
+        &mut self.hidden
+    }
+
     pub(super) fn set_default_cell(&mut self, cell: Option<Cell>) {
         self.default_cell = cell
     }
@@ -303,22 +321,63 @@ impl DoubleBuffer {
     }
 }
 
+/// Reports the mouse in screen pixels instead of cells, in the same SGR
+/// format that mouse capture turns on.
+const ENABLE_PIXEL_MOUSE: &str = "\x1b[?1016h";
+const DISABLE_PIXEL_MOUSE: &str = "\x1b[?1016l";
+
+/// The terminal modes the renderer turns on, and off again when it is
+/// dropped.
+#[derive(Debug, Copy, Clone)]
+pub(super) struct TerminalModes {
+    alternate_screen: bool,
+    mouse_capture: bool,
+    pixel_mouse: bool,
+    focus_events: bool,
+    bracketed_paste: bool,
+}
+
+impl TerminalModes {
+    fn from_config(config: &AppConfig) -> Self {
+        Self {
+            alternate_screen: config.alternate_screen,
+            mouse_capture: config.mouse_capture,
+            pixel_mouse: config.mouse_capture && config.pixel_mouse && mouse::cell_size().is_some(),
+            focus_events: config.focus_events,
+            bracketed_paste: config.bracketed_paste,
+        }
+    }
+}
+
 pub(super) struct Renderer {
     buffer: DoubleBuffer,
     redraw: bool,
+    /// `None` for a renderer that never touches the terminal.
+    modes: Option<TerminalModes>,
 }
 
 impl Renderer {
-    pub(crate) fn new() -> io::Result<Self> {
+    pub(crate) fn new(config: &AppConfig) -> io::Result<Self> {
         let (cols, rows) = terminal::size()?;
+        let modes = TerminalModes::from_config(config);
         let new = Self {
             buffer: DoubleBuffer::from_values(rows, cols),
             redraw: false,
+            modes: Some(modes),
         };
-        Self::init()?;
+        Self::init(modes)?;
         Ok(new)
     }
 
+    /// Creates a renderer that never touches the terminal.
+    pub(crate) fn headless(size: U16Vec2) -> Self {
+        Self {
+            buffer: DoubleBuffer::from_size(size),
+            redraw: false,
+            modes: None,
+        }
+    }
+
     pub(crate) fn render(&mut self) -> io::Result<()> {
         let mut stdout = stdout();
         stdout.queue(crossterm::style::ResetColor)?;
@@ -359,10 +418,19 @@ impl Renderer {
         self.redraw = true;
     }
 
+    /// Returns whether the terminal reports the mouse in screen pixels.
+    pub(super) fn pixel_mouse(&self) -> bool {
+        self.modes.is_some_and(|modes| modes.pixel_mouse)
+    }
+
     pub(super) fn size(&self) -> U16Vec2 {
         self.buffer.size()
     }
 
+    pub(super) fn cells_mut(&mut self) -> &mut [Cell] {
+        self.buffer.cells_mut()
+    }
+
     pub(super) fn set_terminal_styling(
         stdout: &mut Stdout,
         style: &ContentStyle,
@@ -407,27 +475,69 @@ impl Renderer {
         Ok(*new)
     }
 
-    pub(super) fn init() -> io::Result<()> {
+    pub(super) fn init(modes: TerminalModes) -> io::Result<()> {
         terminal::enable_raw_mode()?;
-        execute!(stdout(), cursor::Hide, terminal::EnterAlternateScreen)?;
+        let mut stdout = stdout();
+        if modes.alternate_screen {
+            stdout.queue(terminal::EnterAlternateScreen)?;
+        } else {
+            stdout.queue(terminal::Clear(terminal::ClearType::All))?;
+        }
+        if modes.mouse_capture {
+            stdout.queue(event::EnableMouseCapture)?;
+        }
+        if modes.pixel_mouse {
+            stdout.queue(style::Print(ENABLE_PIXEL_MOUSE))?;
+        }
+        if modes.focus_events {
+            stdout.queue(event::EnableFocusChange)?;
+        }
+        if modes.bracketed_paste {
+            stdout.queue(event::EnableBracketedPaste)?;
+        }
+        stdout.queue(cursor::Hide)?;
+        stdout.flush()?;
         let original_hook = take_hook();
         set_hook(Box::new(move |panic_info| {
             // intentionally ignore errors here since we're already in a panic
-            let _ = Self::shutdown();
+            let _ = Self::shutdown(modes);
             original_hook(panic_info);
         }));
         Ok(())
     }
 
-    pub(super) fn shutdown() -> io::Result<()> {
-        execute!(stdout(), cursor::Show, terminal::LeaveAlternateScreen,)?;
+    pub(super) fn shutdown(modes: TerminalModes) -> io::Result<()> {
+        let mut stdout = stdout();
+        if modes.pixel_mouse {
+            stdout.queue(style::Print(DISABLE_PIXEL_MOUSE))?;
+        }
+        if modes.mouse_capture {
+            stdout.queue(event::DisableMouseCapture)?;
+        }
+        if modes.focus_events {
+            stdout.queue(event::DisableFocusChange)?;
+        }
+        if modes.bracketed_paste {
+            stdout.queue(event::DisableBracketedPaste)?;
+        }
+        if modes.alternate_screen {
+            stdout.queue(terminal::LeaveAlternateScreen)?;
+        } else {
+            // leave the prompt below whatever was drawn last
+            stdout.queue(style::ResetColor)?;
+            stdout.queue(cursor::MoveTo(0, terminal::size()?.1))?;
+        }
+        stdout.queue(cursor::Show)?;
+        stdout.flush()?;
         terminal::disable_raw_mode()
     }
 }
 
 impl Drop for Renderer {
     fn drop(&mut self) {
-        let _ = Self::shutdown();
+        if let Some(modes) = self.modes {
+            let _ = Self::shutdown(modes);
+        }
     }
 }
 
@@ -447,17 +557,57 @@ impl Circle {
     }
 }
 
+/// Returns how much a transform scales by, if it keeps circles round.
+fn conformal_scale(transform: Affine2) -> Option<f32> {
+    let (x_axis, y_axis) = (transform.matrix2.x_axis, transform.matrix2.y_axis);
+    let scale = x_axis.length();
+    let tolerance = 1e-4 * scale.max(1.0);
+    let round = (y_axis.length() - scale).abs() <= tolerance
+        && x_axis.dot(y_axis).abs() <= tolerance * scale;
+    round.then_some(scale)
+}
+
 impl SimpleCanvas {
-    pub(crate) fn new() -> io::Result<Self> {
+    pub(crate) fn new(config: &AppConfig) -> io::Result<Self> {
         Ok(Self {
-            renderer: Renderer::new()?,
+            renderer: Renderer::new(config)?,
+            transforms: Default::default(),
+            clips: vec![],
+            depth: vec![],
+            palette: Palette::default(),
+            blend_mode: BlendMode::default(),
+            linear_blending: true,
+            color_mode: ColorMode::default(),
+            dither: Dither::default(),
+            ascii: None,
         })
     }
 
     pub(crate) fn render(&mut self) -> io::Result<()> {
+        if let Some(ascii) = self.ascii.take() {
+            self.encode_ascii(&ascii);
+            self.ascii = Some(ascii);
+        }
+        self.reduce_colors();
         self.renderer.render()
     }
 
+    /// Creates a canvas of `size` cells that never touches the terminal.
+    pub(crate) fn headless(size: U16Vec2) -> Self {
+        Self {
+            renderer: Renderer::headless(size),
+            transforms: Default::default(),
+            clips: vec![],
+            depth: vec![],
+            palette: Palette::default(),
+            blend_mode: BlendMode::default(),
+            linear_blending: true,
+            color_mode: ColorMode::default(),
+            dither: Dither::default(),
+            ascii: None,
+        }
+    }
+
     pub(super) fn half_block_position_to_rendered_position(&self, pos: U16Vec2) -> Option<U16Vec2> {
         let canvas_size = self.size();
         if pos.x >= canvas_size.x || pos.y >= canvas_size.y {
@@ -467,78 +617,144 @@ impl SimpleCanvas {
         Some(U16Vec2::new(pos.x, pos.y / 2))
     }
 
+    /// Draws `color` at `pos` with the current blend mode.
     pub(super) fn draw(&mut self, pos: U16Vec2, color: Option<Color>) {
+        let blended = color
+            .filter(|_| self.blend_mode != BlendMode::Normal)
+            .and_then(|color| self.palette.to_rgb(color))
+            .map(|color| {
+                let backdrop = self.background_rgb_at_or_default(pos);
+                let Rgb { r, g, b } = self.blend_mode.blend(color, backdrop);
+                Color::Rgb { r, g, b }
+            });
+        self.put(pos, blended.or(color));
+    }
+
+    /// Sets the color at `pos`, replacing what is there whatever the blend
+    /// mode.
+    pub(super) fn put(&mut self, pos: U16Vec2, color: Option<Color>) {
+        if self.is_visible(pos) {
+            self.set_pixel(pos, color);
+        }
+    }
+
+    /// Sets the color at `pos`, even outside the clip.
+    pub(super) fn set_pixel(&mut self, pos: U16Vec2, color: Option<Color>) {
         if let Some(mut cell) = self
             .half_block_position_to_rendered_position(pos)
             .and_then(|pos| self.renderer.buffer.at_mut(pos))
-            .map(BlockCellMut::wrap)
+            .map(|cell| {
+                cell.text = false;
+                BlockCellMut::wrap(cell)
+            })
         {
-            if pos.y % 2 == 0 {
-                cell.set_top(color);
-            } else {
+            if pos.y % 2 == 1 {
                 cell.set_bottom(color);
+            } else {
+                cell.set_top(color);
             }
         }
     }
 
+    /// Draws `color` over what is already at `pos`, weighted by how much of
+    /// the pixel is covered.
+    pub(super) fn blend(&mut self, pos: U16Vec2, color: Rgb<u8>, coverage: f32) {
+        let background_color = self.background_rgb_at_or_default(pos);
+        let color = self.blend_mode.blend(color, background_color);
+        let Rgb { r, g, b } = mix(background_color, color, coverage, self.linear_blending);
+        self.put(pos, Some(Color::Rgb { r, g, b }));
+    }
+
+    /// Fills closed contours with anti-aliased edges.
+    pub(super) fn fill_contours(&mut self, contours: &[Vec<Vec2>], rule: FillRule, color: Rgb<u8>) {
+        self.paint_contours(contours, rule, &Paint::Solid(color));
+    }
+
+    /// Fills closed contours with anti-aliased edges, taking the color of
+    /// each pixel from `paint`.
+    pub(super) fn paint_contours(&mut self, contours: &[Vec<Vec2>], rule: FillRule, paint: &Paint) {
+        let transform = self.full_transform();
+        let contours: Vec<Vec<Vec2>> = contours
+            .iter()
+            .map(|contour| {
+                contour
+                    .iter()
+                    .map(|point| transform.transform_point2(*point))
+                    .collect()
+            })
+            .collect();
+        // paints are placed in the same coordinates as the shape
+        let to_world = transform.inverse();
+
+        let coverage = rasterize(&contours, rule, IVec2::ZERO, self.size().as_ivec2());
+        for (pos, coverage) in coverage.iter() {
+            let color = paint.color_at(to_world.transform_point2(pos.as_vec2()));
+            self.blend(pos.as_u16vec2(), color, coverage);
+        }
+    }
+
     pub(super) fn draw_aa_circle(&mut self, pos: Vec2, circle: Circle) {
         if circle.radius <= 0.0 {
             return;
         }
 
-        let color = circle.stroke_color.unwrap_or(Rgb {
-            r: 255,
-            g: 255,
-            b: 255,
-        });
+        let paint = circle.stroke_color.clone().unwrap_or_default();
+        let transform = self.full_transform();
+        let Some(scale) = conformal_scale(transform) else {
+            // the transform squashes the circle into an ellipse
+            let ring = |radius| {
+                let mut contour = arc_points(pos, radius, 0.0, TAU);
+                contour.pop();
+                contour
+            };
+            let mut contours = vec![ring(circle.radius + circle.outer_stroke())];
+            if circle.radius > circle.inner_stroke() {
+                contours.push(ring(circle.radius - circle.inner_stroke()));
+            }
+            self.paint_contours(&contours, FillRule::EvenOdd, &paint);
+            return;
+        };
+        let to_world = transform.inverse();
+        let pos = transform.transform_point2(pos);
+        let circle = Circle {
+            radius: circle.radius * scale,
+            outer_stroke: Some(circle.outer_stroke() * scale),
+            inner_stroke: Some(circle.inner_stroke() * scale),
+            stroke_color: None,
+        };
 
-        let outer_stroke_sq =
-            (circle.radius + circle.outer_stroke()) * (circle.radius + circle.outer_stroke());
-        let inner_stroke_sq =
-            (circle.radius - circle.inner_stroke()) * (circle.radius - circle.inner_stroke());
-        let span_vector = Vec2::new(
-            circle.radius + circle.outer_stroke(),
-            circle.radius + circle.outer_stroke(),
-        );
+        let outer = circle.radius + circle.outer_stroke();
+        let inner = circle.radius - circle.inner_stroke();
+        if outer <= inner.max(0.0) {
+            return;
+        }
+        // pixels further than this from the edges are fully in or out
+        let reach_sq = (outer + 0.5) * (outer + 0.5);
+        let hole_sq = (inner - 0.5).max(0.0) * (inner - 0.5).max(0.0);
 
-        let top_left = (pos - span_vector + Vec2::ONE / 2.0).floor().as_u16vec2();
-        let bottom_right = (pos + span_vector + Vec2::ONE / 2.0).ceil().as_u16vec2();
+        let size = self.size().as_vec2();
+        let top_left = (pos - Vec2::splat(outer + 0.5)).floor().max(Vec2::ZERO);
+        let bottom_right = (pos + Vec2::splat(outer + 0.5)).ceil().min(size);
 
-        for y in top_left.y..bottom_right.y {
-            for x in top_left.x..bottom_right.x {
+        for y in top_left.y as u16..bottom_right.y as u16 {
+            for x in top_left.x as u16..bottom_right.x as u16 {
                 let canvas_pos = U16Vec2::new(x, y);
+                let dist_sq = canvas_pos.as_vec2().distance_squared(pos);
+                if dist_sq >= reach_sq || dist_sq < hole_sq {
+                    continue;
+                }
 
-                let get_sub_pixel_points = |pow_of_2: usize| {
-                    let divisions = pow(2usize, pow_of_2);
-                    let step = 1.0 / divisions as f32;
-                    let mut points: Vec<Vec2> =
-                        Vec::with_capacity((divisions + 1) * (divisions + 1));
-
-                    for i in 0..=divisions {
-                        for j in 0..=divisions {
-                            let delta =
-                                Vec2::new(j as f32 * step, i as f32 * step) - Vec2::ONE / 2.0;
-                            points.push(canvas_pos.as_vec2() + delta);
-                        }
-                    }
-                    points
-                };
+                // the coverage of a pixel by a disc is close to how far the
+                // edge is past the pixel centre, plus half a pixel
+                let dist = dist_sq.sqrt();
+                let disc = |radius: f32| (radius - dist + 0.5).clamp(0.0, 1.0);
+                let coverage = disc(outer) - if inner > 0.0 { disc(inner) } else { 0.0 };
+                if coverage <= 0.0 {
+                    continue;
+                }
 
-                let pixel_vertices = get_sub_pixel_points(1);
-                if pixel_vertices
-                    .iter()
-                    .all(|p| p.distance_squared(pos) > outer_stroke_sq)
-                {
-                    // do nothing
-                    // self.draw_with_color(canvas_pos, Color::White);
-                } else if pixel_vertices
-                    .iter()
-                    .all(|p| p.distance_squared(pos) < inner_stroke_sq)
-                {
-                } else if pixel_vertices.iter().all(|p| {
-                    let dist_sq = p.distance_squared(pos);
-                    dist_sq <= outer_stroke_sq && dist_sq >= inner_stroke_sq
-                }) {
+                let color = paint.color_at(to_world.transform_point2(canvas_pos.as_vec2()));
+                if coverage >= 1.0 {
                     self.draw(
                         canvas_pos,
                         Some(Color::Rgb {
@@ -548,38 +764,14 @@ impl SimpleCanvas {
                         }),
                     );
                 } else {
-                    // on the edge
-                    let sub_pixel_vertices = get_sub_pixel_points(2);
-                    let count = sub_pixel_vertices
-                        .iter()
-                        .filter(|p| {
-                            let dist_sq = p.distance_squared(pos);
-                            dist_sq <= outer_stroke_sq && dist_sq >= inner_stroke_sq
-                        })
-                        .count() as f32;
-
-                    let magnitude = count / sub_pixel_vertices.len() as f32;
-                    // let sin_magnitude = (magnitude * PI).sin();
-                    let lerp = |l: f32, r: f32, v: f32| l + (r - l) * v;
-                    let background_color = self.background_rgb_at_or_default(canvas_pos);
-                    self.draw(
-                        canvas_pos,
-                        Some(Color::Rgb {
-                            r: lerp(background_color.r.into(), color.r.into(), magnitude) as u8,
-                            g: lerp(background_color.g.into(), color.g.into(), magnitude) as u8,
-                            b: lerp(background_color.b.into(), color.b.into(), magnitude) as u8,
-                        }),
-                    );
+                    self.blend(canvas_pos, color, coverage);
                 }
             }
         }
     }
 
     pub(super) fn draw_line(&mut self, start: IVec2, end: IVec2, color: Option<Color>) {
-        for (x, y) in Bresenham::new(
-            (start.x, start.y),
-            (end.x, end.y),
-        ) {
+        for (x, y) in Bresenham::new((start.x, start.y), (end.x, end.y)) {
             if x < 0 || y < 0 {
                 continue;
             }
@@ -589,8 +781,9 @@ impl SimpleCanvas {
     }
 
     pub(super) fn draw_aa_line(&mut self, start: Vec2, end: Vec2, color: Option<Rgb<u8>>) {
-        let lerp = |l: f32, r: f32, v: f32| l + (r - l) * v;
-
+        let transform = self.full_transform();
+        let start = transform.transform_point2(start);
+        let end = transform.transform_point2(end);
         let color = color.unwrap_or(Rgb {
             r: 255,
             g: 255,
@@ -599,15 +792,7 @@ impl SimpleCanvas {
 
         for ((x, y), magnitude) in XiaolinWu::<f32, i32>::new((start.x, start.y), (end.x, end.y)) {
             let canvas_pos = U16Vec2::new(x as u16, y as u16);
-            let background_color = self.background_rgb_at_or_default(canvas_pos);
-            self.draw(
-                canvas_pos,
-                Some(Color::Rgb {
-                    r: lerp(background_color.r.into(), color.r.into(), magnitude) as u8,
-                    g: lerp(background_color.g.into(), color.g.into(), magnitude) as u8,
-                    b: lerp(background_color.b.into(), color.b.into(), magnitude) as u8,
-                }),
-            );
+            self.blend(canvas_pos, color, magnitude);
         }
     }
 
@@ -623,14 +808,14 @@ impl SimpleCanvas {
 
         let print_pos = alignment.apply(size);
 
-        let end_x = (print_pos.x + (total_width + 1) / 2).min(size.x);
+        let end_x = (print_pos.x + total_width.div_ceil(2)).min(size.x);
         let start_x = end_x.saturating_sub(total_width);
 
-        let end_y = (print_pos.y + (total_height + 1) / 2).min(size.y);
+        let end_y = (print_pos.y + total_height.div_ceil(2)).min(size.y);
         let start_y = end_y.saturating_sub(total_height);
 
         let line_start_x = start_x + style.left_width();
-        let line_start_y = start_y + (style.top_width() + 1) / 2;
+        let line_start_y = start_y + style.top_width().div_ceil(2);
 
         let canvas_start_x = line_start_x;
         let canvas_start_y = line_start_y * 2;
@@ -646,8 +831,12 @@ impl SimpleCanvas {
 
         for y in box_start_y..box_end_y {
             for x in box_start_x..box_end_x {
-                if let Some(color) = content.style().background_color {
-                    self.draw(U16Vec2::new(x, y), Some(color));
+                let pos = U16Vec2::new(x, y);
+                if let Some(paint) = content.background_paint() {
+                    let Rgb { r, g, b } = paint.color_at(pos.as_vec2());
+                    self.put(pos, Some(Color::Rgb { r, g, b }));
+                } else if let Some(color) = content.style().background_color {
+                    self.put(pos, Some(color));
                 }
             }
         }
@@ -656,22 +845,22 @@ impl SimpleCanvas {
             for x in box_start_x..box_end_x {
                 if let Some(color) = content.style().border_style.left_border {
                     if x == box_start_x {
-                        self.draw(U16Vec2::new(x, y), Some(color));
+                        self.put(U16Vec2::new(x, y), Some(color));
                     }
                 }
                 if let Some(color) = content.style().border_style.right_border {
                     if x == box_end_x - 1 {
-                        self.draw(U16Vec2::new(x, y), Some(color));
+                        self.put(U16Vec2::new(x, y), Some(color));
                     }
                 }
                 if let Some(color) = content.style().border_style.top_border {
                     if y == box_start_y {
-                        self.draw(U16Vec2::new(x, y), Some(color));
+                        self.put(U16Vec2::new(x, y), Some(color));
                     }
                 }
                 if let Some(color) = content.style().border_style.bottom_border {
                     if y == box_end_y - 1 {
-                        self.draw(U16Vec2::new(x, y), Some(color));
+                        self.put(U16Vec2::new(x, y), Some(color));
                     }
                 }
             }
@@ -679,6 +868,13 @@ impl SimpleCanvas {
 
         // write content
         for (i, c) in content.content().chars().enumerate() {
+            // a cell is only written if both of its pixels are visible
+            let x = i as u16 + line_start_x;
+            if !self.is_visible(U16Vec2::new(x, canvas_start_y))
+                || !self.is_visible(U16Vec2::new(x, canvas_start_y + 1))
+            {
+                continue;
+            }
             if let Some(cell) = self
                 .renderer
                 .buffer
@@ -686,6 +882,14 @@ impl SimpleCanvas {
             {
                 cell.c = c;
                 cell.style = content.style().content_style();
+                cell.text = true;
+                if let Some(paint) = content.background_paint() {
+                    // a text cell covers two pixels, so sample between them
+                    let x = (i as u16 + line_start_x) as f32;
+                    let middle = Vec2::new(x, canvas_start_y as f32 + 0.5);
+                    let Rgb { r, g, b } = paint.color_at(middle);
+                    cell.style.background_color = Some(Color::Rgb { r, g, b });
+                }
             }
         }
     }
@@ -696,10 +900,10 @@ impl SimpleCanvas {
             .and_then(|pos| self.renderer.buffer.at(pos))
             .map(BlockCell::wrap)
         {
-            if pos.y % 2 == 0 {
-                cell.at_top()
-            } else {
+            if pos.y % 2 == 1 {
                 cell.at_bottom()
+            } else {
+                cell.at_top()
             }
         } else {
             None
@@ -709,13 +913,7 @@ impl SimpleCanvas {
     fn background_rgb_at_or_default(&self, pos: U16Vec2) -> Rgb<u8> {
         self.color_at(pos)
             .or_else(|| self.renderer.get_background_color())
-            .and_then(|color| {
-                if let Color::Rgb { r, g, b } = color {
-                    Some(Rgb::new(r, g, b))
-                } else {
-                    None
-                }
-            })
+            .and_then(|color| self.palette.to_rgb(color))
             .unwrap_or_else(|| Rgb::new(0, 0, 0))
     }
 }
@@ -747,7 +945,7 @@ mod test {
 
     #[test]
     fn render() {
-        let mut renderer = Renderer::new().unwrap();
+        let mut renderer = Renderer::new(&AppConfig::default()).unwrap();
         assert!(renderer.render().is_ok());
     }
 }
@@ -0,0 +1,363 @@
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+use glam::Vec2;
+
+/// The furthest a flattened curve may stray from the true curve, in pixels.
+const TOLERANCE: f32 = 0.1;
+
+/// The most segments an arc is split into, however big it is.
+const MAX_ARC_SEGMENTS: f32 = 4096.0;
+
+/// Returns points along a circular arc from `start` to `end` radians,
+/// including both end points.
+pub(crate) fn arc_points(center: Vec2, radius: f32, start: f32, end: f32) -> Vec<Vec2> {
+    ellipse_points(center, Vec2::splat(radius), 0.0, start, end)
+}
+
+/// Returns points along an elliptical arc from `start` to `end` radians,
+/// including both end points. The angles are measured before the ellipse is
+/// rotated.
+///
+/// Arcs sweeping more than a full turn are cut down to one turn, and arcs
+/// with a position, size or angle that is not finite have no points.
+pub(crate) fn ellipse_points(
+    center: Vec2,
+    radii: Vec2,
+    rotation: f32,
+    start: f32,
+    end: f32,
+) -> Vec<Vec2> {
+    let angles = Vec2::new(start, end);
+    if !(center.is_finite() && radii.is_finite() && angles.is_finite() && rotation.is_finite()) {
+        return vec![];
+    }
+
+    let sweep = (end - start).clamp(-TAU, TAU);
+    let radius = radii.max_element();
+    let segments = if radius > TOLERANCE {
+        let step = 2.0 * (1.0 - TOLERANCE / radius).acos();
+        (sweep.abs() / step).ceil().clamp(1.0, MAX_ARC_SEGMENTS) as usize
+    } else {
+        1
+    };
+    let rotation = Vec2::from_angle(rotation);
+    (0..=segments)
+        .map(|i| {
+            let angle = start + sweep * i as f32 / segments as f32;
+            center + rotation.rotate(Vec2::from_angle(angle) * radii)
+        })
+        .collect()
+}
+
+/// Returns points along an SVG-style elliptical arc from `start` to `end`,
+/// including both end points.
+///
+/// Radii that are too small to reach the end point are scaled up, and an arc
+/// with a zero radius is a straight line.
+pub(crate) fn svg_arc_points(
+    start: Vec2,
+    end: Vec2,
+    radii: Vec2,
+    rotation: f32,
+    large_arc: bool,
+    sweep: bool,
+) -> Vec<Vec2> {
+    let mut radii = radii.abs();
+    if start == end {
+        return vec![start];
+    }
+    if radii.min_element() <= f32::EPSILON {
+        return vec![start, end];
+    }
+
+    // find the centre, following the SVG implementation notes
+    let rotation_vec = Vec2::from_angle(rotation);
+    let half = Vec2::from_angle(-rotation).rotate((start - end) / 2.0);
+    let lambda = (half / radii).length_squared();
+    if lambda > 1.0 {
+        radii *= lambda.sqrt();
+    }
+    let (rx2, ry2) = (radii.x * radii.x, radii.y * radii.y);
+    let (x2, y2) = (half.x * half.x, half.y * half.y);
+    let numerator = (rx2 * ry2 - rx2 * y2 - ry2 * x2).max(0.0);
+    let mut scale = (numerator / (rx2 * y2 + ry2 * x2)).sqrt();
+    if large_arc == sweep {
+        scale = -scale;
+    }
+    let center_local = Vec2::new(radii.x * half.y / radii.y, -radii.y * half.x / radii.x) * scale;
+    let center = rotation_vec.rotate(center_local) + (start + end) / 2.0;
+
+    let from = (half - center_local) / radii;
+    let to = (-half - center_local) / radii;
+    let start_angle = from.to_angle();
+    let mut sweep_angle = from.angle_to(to);
+    if sweep && sweep_angle < 0.0 {
+        sweep_angle += TAU;
+    } else if !sweep && sweep_angle > 0.0 {
+        sweep_angle -= TAU;
+    }
+
+    let mut points = ellipse_points(
+        center,
+        radii,
+        rotation,
+        start_angle,
+        start_angle + sweep_angle,
+    );
+    // snap the ends so the arc joins its neighbours exactly
+    match points.as_mut_slice() {
+        [first, .., last] => {
+            *first = start;
+            *last = end;
+        }
+        // radii too big to find a centre with
+        _ => return vec![start, end],
+    }
+    points
+}
+
+/// Returns the outline of a rectangle spanning `min..max` with corners
+/// rounded by `radius`.
+pub(crate) fn rounded_rect_contour(min: Vec2, max: Vec2, radius: f32) -> Vec<Vec2> {
+    let radius = radius.clamp(0.0, (max - min).min_element() / 2.0);
+    if radius <= 0.0 {
+        return vec![min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)];
+    }
+
+    let corners = [
+        (Vec2::new(max.x - radius, min.y + radius), -FRAC_PI_2),
+        (Vec2::new(max.x - radius, max.y - radius), 0.0),
+        (Vec2::new(min.x + radius, max.y - radius), FRAC_PI_2),
+        (Vec2::new(min.x + radius, min.y + radius), PI),
+    ];
+    corners
+        .iter()
+        .flat_map(|&(center, start)| arc_points(center, radius, start, start + FRAC_PI_2))
+        .collect()
+}
+
+/// How many times a curve may be split in half while flattening it.
+const MAX_SUBDIVISIONS: u32 = 16;
+
+/// Returns points along a quadratic Bézier curve, including both end points.
+pub(crate) fn quad_points(start: Vec2, control: Vec2, end: Vec2) -> Vec<Vec2> {
+    // every quadratic curve is also a cubic one
+    let first = start + (control - start) * 2.0 / 3.0;
+    let second = end + (control - end) * 2.0 / 3.0;
+    cubic_points(start, first, second, end)
+}
+
+/// Returns points along a cubic Bézier curve, including both end points.
+///
+/// The curve is split in half until each piece is flat enough to be drawn as
+/// a straight line, so gentle curves use few points and tight ones use many.
+pub(crate) fn cubic_points(start: Vec2, first: Vec2, second: Vec2, end: Vec2) -> Vec<Vec2> {
+    let mut points = vec![start];
+    flatten_cubic([start, first, second, end], MAX_SUBDIVISIONS, &mut points);
+    points
+}
+
+fn flatten_cubic(curve: [Vec2; 4], depth: u32, points: &mut Vec<Vec2>) {
+    let [p0, p1, p2, p3] = curve;
+    let chord = p3 - p0;
+    // the distance from the chord between the end points rather than the
+    // line through them, so a curve that overshoots its ends is not flat
+    let distance = |p: Vec2| {
+        let t = if chord.length_squared() < f32::EPSILON {
+            0.0
+        } else {
+            ((p - p0).dot(chord) / chord.length_squared()).clamp(0.0, 1.0)
+        };
+        p.distance(p0 + chord * t)
+    };
+
+    if depth == 0 || distance(p1).max(distance(p2)) <= TOLERANCE {
+        points.push(p3);
+        return;
+    }
+
+    let p01 = p0.lerp(p1, 0.5);
+    let p12 = p1.lerp(p2, 0.5);
+    let p23 = p2.lerp(p3, 0.5);
+    let p012 = p01.lerp(p12, 0.5);
+    let p123 = p12.lerp(p23, 0.5);
+    let mid = p012.lerp(p123, 0.5);
+    flatten_cubic([p0, p01, p012, mid], depth - 1, points);
+    flatten_cubic([mid, p123, p23, p3], depth - 1, points);
+}
+
+/// Returns points along a Catmull-Rom spline that passes through every one
+/// of `points`.
+pub(crate) fn spline_points(points: &[Vec2]) -> Vec<Vec2> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+
+    let last = points.len() - 1;
+    let mut curve = vec![points[0]];
+    for i in 0..last {
+        let before = points[i.saturating_sub(1)];
+        let start = points[i];
+        let end = points[i + 1];
+        let after = points[(i + 2).min(last)];
+
+        let first = start + (end - before) / 6.0;
+        let second = end - (after - start) / 6.0;
+        curve.extend(cubic_points(start, first, second, end).into_iter().skip(1));
+    }
+    curve
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arc_end_points() {
+        let points = arc_points(Vec2::ZERO, 10.0, 0.0, PI);
+        assert!(points.len() > 2);
+        assert!(points[0].abs_diff_eq(Vec2::new(10.0, 0.0), 1e-4));
+        assert!(points[points.len() - 1].abs_diff_eq(Vec2::new(-10.0, 0.0), 1e-4));
+        assert!(points.iter().all(|p| (p.length() - 10.0).abs() < 1e-4));
+    }
+
+    #[test]
+    fn arc_within_tolerance() {
+        let points = arc_points(Vec2::ZERO, 50.0, 0.0, 2.0 * PI);
+        for pair in points.windows(2) {
+            let midpoint = (pair[0] + pair[1]) / 2.0;
+            assert!(50.0 - midpoint.length() <= TOLERANCE + 1e-4);
+        }
+    }
+
+    #[test]
+    fn rotated_ellipse() {
+        let points = ellipse_points(Vec2::ONE, Vec2::new(10.0, 5.0), FRAC_PI_2, 0.0, PI);
+        assert!(points[0].abs_diff_eq(Vec2::new(1.0, 11.0), 1e-4));
+        assert!(points[points.len() - 1].abs_diff_eq(Vec2::new(1.0, -9.0), 1e-4));
+        assert!(points
+            .iter()
+            .all(|p| p.x <= 1.0 + 1e-4 && p.x >= -4.0 - 1e-4));
+    }
+
+    #[test]
+    fn arc_limits() {
+        let full = arc_points(Vec2::ZERO, 10.0, 0.0, TAU);
+        let spun = arc_points(Vec2::ZERO, 10.0, 0.0, 1000.0);
+        assert_eq!(spun.len(), full.len());
+        assert!(spun.last().unwrap().abs_diff_eq(Vec2::new(10.0, 0.0), 1e-3));
+
+        assert!(arc_points(Vec2::ZERO, 10.0, 0.0, f32::INFINITY).is_empty());
+        assert!(arc_points(Vec2::ZERO, 10.0, f32::NAN, PI).is_empty());
+        assert!(arc_points(Vec2::ZERO, f32::INFINITY, 0.0, PI).is_empty());
+        assert!(arc_points(Vec2::ZERO, 1e30, 0.0, TAU).len() <= MAX_ARC_SEGMENTS as usize + 1);
+    }
+
+    #[test]
+    fn svg_arcs() {
+        let start = Vec2::ZERO;
+        let end = Vec2::new(10.0, 0.0);
+
+        // a half circle bulging up or down depending on the sweep
+        let up = svg_arc_points(start, end, Vec2::splat(5.0), 0.0, false, true);
+        let down = svg_arc_points(start, end, Vec2::splat(5.0), 0.0, false, false);
+        assert_eq!((up[0], up[up.len() - 1]), (start, end));
+        assert!(up
+            .iter()
+            .all(|p| p.y <= 1e-4 && (p.distance(Vec2::new(5.0, 0.0)) - 5.0).abs() < 1e-3));
+        assert!(down.iter().all(|p| p.y >= -1e-4));
+
+        // small radii are grown to fit
+        let grown = svg_arc_points(start, end, Vec2::splat(1.0), 0.0, false, true);
+        assert!(grown.iter().any(|p| p.y < -4.9));
+
+        // the large arc goes the long way around
+        let small = svg_arc_points(start, end, Vec2::splat(10.0), 0.0, false, true);
+        let large = svg_arc_points(start, end, Vec2::splat(10.0), 0.0, true, true);
+        let lowest = |points: &[Vec2]| points.iter().map(|p| p.y).fold(0.0, f32::min);
+        assert!(lowest(&small) > -2.0);
+        assert!(lowest(&large) < -18.0);
+
+        let flat = svg_arc_points(start, end, Vec2::ZERO, 0.0, false, true);
+        assert_eq!(flat, vec![start, end]);
+        let endless = svg_arc_points(start, end, Vec2::splat(f32::INFINITY), 0.0, false, true);
+        assert_eq!(endless, vec![start, end]);
+    }
+
+    #[test]
+    fn flat_curves() {
+        let straight = cubic_points(
+            Vec2::ZERO,
+            Vec2::new(1.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(3.0, 0.0),
+        );
+        assert_eq!(straight, vec![Vec2::ZERO, Vec2::new(3.0, 0.0)]);
+
+        // straight, but doubling back on itself past the end point
+        let overshoot = cubic_points(
+            Vec2::ZERO,
+            Vec2::new(10.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(5.0, 0.0),
+        );
+        let furthest = overshoot.iter().map(|p| p.x).fold(0.0, f32::max);
+        assert!(furthest > 8.28 - TOLERANCE, "{furthest}");
+    }
+
+    #[test]
+    fn curves_within_tolerance() {
+        let start = Vec2::ZERO;
+        let control = Vec2::new(20.0, 40.0);
+        let end = Vec2::new(40.0, 0.0);
+        let points = quad_points(start, control, end);
+        assert_eq!(points[0], start);
+        assert_eq!(points[points.len() - 1], end);
+
+        let at = |t: f32| start * (1.0 - t).powi(2) + control * 2.0 * t * (1.0 - t) + end * t * t;
+        for i in 0..=100 {
+            let on_curve = at(i as f32 / 100.0);
+            let nearest = points
+                .windows(2)
+                .map(|pair| {
+                    let segment = pair[1] - pair[0];
+                    let t = ((on_curve - pair[0]).dot(segment) / segment.length_squared())
+                        .clamp(0.0, 1.0);
+                    on_curve.distance(pair[0] + segment * t)
+                })
+                .fold(f32::INFINITY, f32::min);
+            assert!(nearest <= TOLERANCE + 1e-3);
+        }
+    }
+
+    #[test]
+    fn spline_passes_through_points() {
+        let points = [
+            Vec2::ZERO,
+            Vec2::new(10.0, 10.0),
+            Vec2::new(20.0, 0.0),
+            Vec2::new(30.0, 10.0),
+        ];
+        let curve = spline_points(&points);
+        for point in points {
+            assert!(curve.contains(&point));
+        }
+        assert!(curve.len() > points.len());
+    }
+
+    #[test]
+    fn rounded_rect() {
+        let square = rounded_rect_contour(Vec2::ZERO, Vec2::splat(4.0), 0.0);
+        assert_eq!(square.len(), 4);
+
+        let rounded = rounded_rect_contour(Vec2::ZERO, Vec2::splat(4.0), 1.0);
+        assert!(rounded.len() > 8);
+        assert!(rounded
+            .iter()
+            .all(|p| p.cmpge(Vec2::ZERO).all() && p.cmple(Vec2::splat(4.0)).all()));
+
+        // the radius is limited to half the shorter side
+        let pill = rounded_rect_contour(Vec2::ZERO, Vec2::new(10.0, 2.0), 5.0);
+        assert!(pill.iter().all(|p| p.y >= -1e-4 && p.y <= 2.0 + 1e-4));
+    }
+}
@@ -0,0 +1,389 @@
+use std::f32::consts::TAU;
+
+use glam::Vec2;
+
+use crate::style::{Dash, LineCap, LineJoin, Stroke};
+
+use super::{
+    raster::{oriented, signed_area},
+    shape::arc_points,
+};
+
+/// Returns contours covering a polyline drawn with `stroke`.
+///
+/// The contours overlap and are meant to be filled together with
+/// [`FillRule::NonZero`](crate::style::FillRule::NonZero).
+pub(crate) fn stroke_outline(points: &[Vec2], closed: bool, stroke: &Stroke) -> Vec<Vec<Vec2>> {
+    if let Some(dash) = &stroke.dash {
+        let solid = Stroke {
+            dash: None,
+            ..stroke.clone()
+        };
+        return dash_polyline(points, closed, dash)
+            .iter()
+            .flat_map(|piece| stroke_outline(piece, false, &solid))
+            .collect();
+    }
+
+    let mut points: Vec<Vec2> = points.to_vec();
+    points.dedup_by(|b, a| a.distance_squared(*b) < f32::EPSILON);
+    if closed
+        && points.len() > 2
+        && points[0].distance_squared(points[points.len() - 1]) < f32::EPSILON
+    {
+        points.pop();
+    }
+    if points.is_empty() || stroke.width <= 0.0 {
+        return vec![];
+    }
+
+    let half_width = stroke.width / 2.0;
+    if points.len() == 1 {
+        // a zero length line is only visible through its caps
+        return match stroke.cap {
+            LineCap::Butt => vec![],
+            LineCap::Round => vec![disc(points[0], half_width)],
+            LineCap::Square => {
+                let left = points[0] - Vec2::X * half_width;
+                vec![square_cap(left, Vec2::X * stroke.width, half_width)]
+            }
+        };
+    }
+
+    let segment_count = if closed {
+        points.len()
+    } else {
+        points.len() - 1
+    };
+    let segments: Vec<(Vec2, Vec2)> = (0..segment_count)
+        .map(|i| (points[i], points[(i + 1) % points.len()]))
+        .collect();
+    let normal = |(a, b): (Vec2, Vec2)| (b - a).normalize().perp() * half_width;
+
+    let mut contours: Vec<Vec<Vec2>> = segments
+        .iter()
+        .map(|&(a, b)| {
+            let n = normal((a, b));
+            oriented(vec![a + n, b + n, b - n, a - n])
+        })
+        .collect();
+
+    let joins = if closed {
+        segment_count
+    } else {
+        segment_count - 1
+    };
+    for i in 0..joins {
+        let incoming = segments[i];
+        let outgoing = segments[(i + 1) % segment_count];
+        let corner = incoming.1;
+        contours.extend(join(corner, normal(incoming), normal(outgoing), stroke));
+    }
+
+    if !closed {
+        let (first, last) = (segments[0], segments[segment_count - 1]);
+        contours.extend(cap(first.0, first.0 - first.1, half_width, stroke.cap));
+        contours.extend(cap(last.1, last.1 - last.0, half_width, stroke.cap));
+    }
+
+    contours
+}
+
+/// Splits a polyline into the pieces that are drawn by a dash pattern.
+///
+/// Closed polylines that start and end in the middle of a dash have the two
+/// halves joined, so the seam is not visible.
+pub(crate) fn dash_polyline(points: &[Vec2], closed: bool, dash: &Dash) -> Vec<Vec<Vec2>> {
+    let mut pattern = dash.pattern.clone();
+    if pattern.len() % 2 == 1 {
+        pattern.extend_from_within(..);
+    }
+    let period: f32 = pattern.iter().sum();
+    let mut path = points.to_vec();
+    if closed && !path.is_empty() {
+        path.push(path[0]);
+    }
+    if pattern.iter().any(|length| *length < 0.0) || period <= f32::EPSILON {
+        return vec![path];
+    }
+
+    // find where in the pattern the line starts
+    let mut index = 0;
+    let mut remaining = dash.offset.rem_euclid(period);
+    while remaining >= pattern[index] {
+        remaining -= pattern[index];
+        index = (index + 1) % pattern.len();
+    }
+    remaining = pattern[index] - remaining;
+    let starts_on = index % 2 == 0;
+
+    let mut pieces = vec![];
+    let mut current: Vec<Vec2> = if starts_on && !path.is_empty() {
+        vec![path[0]]
+    } else {
+        vec![]
+    };
+    for pair in path.windows(2) {
+        let (start, end) = (pair[0], pair[1]);
+        let length = start.distance(end);
+        let mut travelled = 0.0;
+        while length - travelled > remaining {
+            travelled += remaining;
+            let point = start.lerp(end, travelled / length);
+            if index % 2 == 0 {
+                current.push(point);
+                pieces.push(std::mem::take(&mut current));
+            } else {
+                current.push(point);
+            }
+            index = (index + 1) % pattern.len();
+            remaining = pattern[index];
+        }
+        remaining -= length - travelled;
+        if index % 2 == 0 {
+            current.push(end);
+        }
+    }
+    let ends_on = index % 2 == 0;
+    if ends_on && !current.is_empty() {
+        pieces.push(current);
+    }
+
+    if closed && starts_on && ends_on && pieces.len() > 1 {
+        let first = pieces.remove(0);
+        pieces.last_mut().unwrap().extend(first.into_iter().skip(1));
+    }
+    pieces.retain(|piece| !piece.is_empty());
+    pieces
+}
+
+/// Returns the contours that fill the gap on the outside of a corner, given
+/// the normals of the segments meeting there.
+fn join(corner: Vec2, n0: Vec2, n1: Vec2, stroke: &Stroke) -> Vec<Vec<Vec2>> {
+    let half_width = stroke.width / 2.0;
+    if let LineJoin::Round = stroke.join {
+        return vec![disc(corner, half_width)];
+    }
+
+    let mut contours = vec![];
+    for side in [1.0, -1.0] {
+        let (a, b) = (n0 * side, n1 * side);
+        let wedge = vec![corner, corner + a, corner + b];
+        if signed_area(&wedge).abs() <= f32::EPSILON {
+            continue;
+        }
+
+        // the outer side of the corner is the one the outgoing segment
+        // turns away from
+        let outer = a.dot(n1.perp() * side) < 0.0;
+        let miter = (a + b) * (half_width * half_width / (half_width * half_width + a.dot(b)));
+        if outer
+            && stroke.join == LineJoin::Miter
+            && miter.length() <= stroke.miter_limit * half_width
+        {
+            contours.push(oriented(vec![
+                corner,
+                corner + a,
+                corner + miter,
+                corner + b,
+            ]));
+        } else {
+            contours.push(oriented(wedge));
+        }
+    }
+    contours
+}
+
+/// Returns the contours that finish an open end at `end`, where `outward`
+/// points away from the line.
+fn cap(end: Vec2, outward: Vec2, half_width: f32, cap: LineCap) -> Vec<Vec<Vec2>> {
+    match cap {
+        LineCap::Butt => vec![],
+        LineCap::Round => vec![disc(end, half_width)],
+        LineCap::Square => {
+            let outward = outward.normalize() * half_width;
+            vec![oriented(square_cap(end, outward, half_width))]
+        }
+    }
+}
+
+/// The half square of `half_width` that sticks out of `end` along `outward`.
+fn square_cap(end: Vec2, outward: Vec2, half_width: f32) -> Vec<Vec2> {
+    let n = outward.normalize().perp() * half_width;
+    vec![end + n, end + n + outward, end - n + outward, end - n]
+}
+
+fn disc(center: Vec2, radius: f32) -> Vec<Vec2> {
+    let mut points = arc_points(center, radius, 0.0, TAU);
+    points.pop();
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::IVec2;
+
+    use super::*;
+    use crate::{
+        engine::raster::rasterize,
+        style::{FillRule, StrokeLike},
+    };
+
+    #[test]
+    fn horizontal_line() {
+        let contours = stroke_outline(
+            &[Vec2::new(2.0, 5.0), Vec2::new(8.0, 5.0)],
+            false,
+            &Stroke::with_width(2.0),
+        );
+        assert_eq!(contours.len(), 1);
+        let coverage = rasterize(&contours, FillRule::NonZero, IVec2::ZERO, IVec2::splat(20));
+        assert!((coverage.at(IVec2::new(5, 5)) - 1.0).abs() < 1e-4);
+        assert!((coverage.at(IVec2::new(5, 4)) - 0.5).abs() < 1e-4);
+        assert!((coverage.at(IVec2::new(5, 6)) - 0.5).abs() < 1e-4);
+        assert_eq!(coverage.at(IVec2::new(5, 3)), 0.0);
+    }
+
+    #[test]
+    fn corners_are_filled() {
+        let points = [
+            Vec2::new(2.0, 2.0),
+            Vec2::new(10.0, 2.0),
+            Vec2::new(10.0, 10.0),
+        ];
+        let contours = stroke_outline(
+            &points,
+            false,
+            &Stroke::with_width(4.0).join(LineJoin::Bevel),
+        );
+        let coverage = rasterize(&contours, FillRule::NonZero, IVec2::ZERO, IVec2::splat(20));
+        // the bevel fills the outer corner and cuts it off diagonally
+        assert_eq!(coverage.at(IVec2::new(10, 1)), 1.0);
+        assert!((coverage.at(IVec2::new(11, 1)) - 0.5).abs() < 1e-4);
+        assert_eq!(coverage.at(IVec2::new(11, 0)), 0.0);
+        // where the two segments overlap
+        assert_eq!(coverage.at(IVec2::new(10, 2)), 1.0);
+    }
+
+    #[test]
+    fn caps() {
+        let points = [Vec2::new(5.0, 5.0), Vec2::new(10.0, 5.0)];
+        let coverage = |cap: LineCap| {
+            let contours = stroke_outline(&points, false, &Stroke::with_width(4.0).cap(cap));
+            rasterize(&contours, FillRule::NonZero, IVec2::ZERO, IVec2::splat(20))
+        };
+
+        let butt = coverage(LineCap::Butt);
+        assert!((butt.at(IVec2::new(5, 5)) - 0.5).abs() < 1e-4);
+        assert_eq!(butt.at(IVec2::new(4, 5)), 0.0);
+
+        let square = coverage(LineCap::Square);
+        assert_eq!(square.at(IVec2::new(4, 5)), 1.0);
+        assert_eq!(square.at(IVec2::new(4, 4)), 1.0);
+        assert!((square.at(IVec2::new(3, 5)) - 0.5).abs() < 1e-4);
+
+        let round = coverage(LineCap::Round);
+        assert_eq!(round.at(IVec2::new(4, 5)), 1.0);
+        assert!(round.at(IVec2::new(4, 3)) < 0.5);
+    }
+
+    #[test]
+    fn joins() {
+        let points = [
+            Vec2::new(2.0, 2.0),
+            Vec2::new(10.0, 2.0),
+            Vec2::new(10.0, 10.0),
+        ];
+        let coverage = |join: LineJoin| {
+            let contours = stroke_outline(&points, false, &Stroke::with_width(4.0).join(join));
+            rasterize(&contours, FillRule::NonZero, IVec2::ZERO, IVec2::splat(20))
+        };
+
+        // next to the outer corner of the stroke at (12, 0)
+        let corner = IVec2::new(11, 1);
+        assert_eq!(coverage(LineJoin::Miter).at(corner), 1.0);
+        assert!((coverage(LineJoin::Bevel).at(corner) - 0.5).abs() < 1e-4);
+        let round = coverage(LineJoin::Round).at(corner);
+        assert!(round > 0.5 && round < 1.0);
+    }
+
+    #[test]
+    fn miter_limit() {
+        // a very sharp turn
+        let points = [
+            Vec2::new(0.0, 10.0),
+            Vec2::new(20.0, 10.0),
+            Vec2::new(0.0, 12.0),
+        ];
+        let sharp = Stroke::with_width(2.0).join(LineJoin::Miter);
+        let spike = |stroke: &Stroke| {
+            let contours = stroke_outline(&points, false, stroke);
+            rasterize(&contours, FillRule::NonZero, IVec2::ZERO, IVec2::splat(40))
+                .at(IVec2::new(23, 10))
+        };
+        assert_eq!(spike(&sharp), 0.0);
+        assert!(spike(&sharp.miter_limit(100.0)) > 0.0);
+    }
+
+    #[test]
+    fn dashes() {
+        let line = [Vec2::ZERO, Vec2::new(10.0, 0.0)];
+        let dash = Dash::new([3.0, 1.0]);
+        let pieces = dash_polyline(&line, false, &dash);
+        assert_eq!(
+            pieces,
+            vec![
+                vec![Vec2::ZERO, Vec2::new(3.0, 0.0)],
+                vec![Vec2::new(4.0, 0.0), Vec2::new(7.0, 0.0)],
+                vec![Vec2::new(8.0, 0.0), Vec2::new(10.0, 0.0)],
+            ]
+        );
+
+        // an offset moves the pattern backwards along the line
+        let pieces = dash_polyline(&line, false, &dash.clone().offset(2.0));
+        assert_eq!(pieces[0], vec![Vec2::ZERO, Vec2::new(1.0, 0.0)]);
+        assert_eq!(pieces[1], vec![Vec2::new(2.0, 0.0), Vec2::new(5.0, 0.0)]);
+
+        // odd patterns are repeated to make them even
+        let pieces = dash_polyline(&line, false, &Dash::new([2.0]));
+        assert_eq!(pieces.len(), 3);
+    }
+
+    #[test]
+    fn dashes_follow_corners() {
+        let corner = [Vec2::ZERO, Vec2::new(2.0, 0.0), Vec2::new(2.0, 2.0)];
+        let pieces = dash_polyline(&corner, false, &Dash::new([3.0, 10.0]));
+        assert_eq!(
+            pieces,
+            vec![vec![Vec2::ZERO, Vec2::new(2.0, 0.0), Vec2::new(2.0, 1.0)]]
+        );
+    }
+
+    #[test]
+    fn closed_dashes_join_at_seam() {
+        let square = [
+            Vec2::ZERO,
+            Vec2::new(4.0, 0.0),
+            Vec2::new(4.0, 4.0),
+            Vec2::new(0.0, 4.0),
+        ];
+        let pieces = dash_polyline(&square, true, &Dash::new([3.0, 2.0]).offset(1.0));
+        assert_eq!(pieces.len(), 3);
+        assert_eq!(
+            pieces[2],
+            vec![Vec2::new(0.0, 2.0), Vec2::ZERO, Vec2::new(2.0, 0.0)]
+        );
+    }
+
+    #[test]
+    fn degenerate() {
+        let stroke = Stroke::default();
+        assert!(stroke_outline(&[Vec2::ONE], false, &stroke).is_empty());
+        assert!(stroke_outline(&[Vec2::ONE, Vec2::ONE], false, &stroke).is_empty());
+        assert!(stroke_outline(&[Vec2::ZERO, Vec2::ONE], false, &stroke.width(0.0)).is_empty());
+
+        // round and square caps still draw a dot
+        let dot = Stroke::with_width(2.0).cap(LineCap::Round);
+        assert!(!stroke_outline(&[Vec2::ONE], false, &dot).is_empty());
+    }
+}
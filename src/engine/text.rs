@@ -0,0 +1,90 @@
+use glam::Vec2;
+
+use crate::{font::BASELINE, style::StrokeText};
+
+use super::SimpleCanvas;
+
+impl SimpleCanvas {
+    /// Draws text with its baseline starting at `pos`.
+    pub(super) fn draw_stroke_text(&mut self, pos: Vec2, text: StrokeText<'_>) {
+        let font = text.font();
+        let scale = text.scale();
+        let rotation = Vec2::from_angle(text.rotation);
+
+        let mut pen = 0.0;
+        for c in text.content.chars() {
+            if let Some(glyph) = font.glyph(c) {
+                for stroke in glyph.strokes.iter() {
+                    let to_canvas = |point: &Vec2| {
+                        let local = Vec2::new(pen + point.x - glyph.left, point.y - BASELINE);
+                        pos + rotation.rotate(local * scale)
+                    };
+                    for segment in stroke.windows(2) {
+                        self.draw_aa_line(
+                            to_canvas(&segment[0]),
+                            to_canvas(&segment[1]),
+                            text.color,
+                        );
+                    }
+                }
+            }
+            pen += font.advance(c);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use glam::{IVec2, U16Vec2};
+
+    use super::*;
+    use crate::style::StrokeTextLike;
+
+    fn draw(text: StrokeText<'_>) -> SimpleCanvas {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(60, 30));
+        canvas.stroke_text(Vec2::new(10.0, 40.0), text);
+        canvas
+    }
+
+    /// The size of the box around every pixel that was drawn.
+    fn drawn_size(canvas: &SimpleCanvas) -> IVec2 {
+        let size = canvas.size().as_ivec2();
+        let drawn: Vec<IVec2> = (0..size.y)
+            .flat_map(|y| (0..size.x).map(move |x| IVec2::new(x, y)))
+            .filter(|pos| canvas.at(*pos).is_some())
+            .collect();
+        let min = drawn.iter().copied().reduce(IVec2::min).unwrap();
+        let max = drawn.iter().copied().reduce(IVec2::max).unwrap();
+        max - min + IVec2::ONE
+    }
+
+    #[test]
+    fn scale_and_rotation() {
+        // a single upright stroke, as tall as the cap height
+        let upright = drawn_size(&draw(StrokeText::new("l").height(20.0)));
+        assert!(upright.y >= 20 && upright.y <= 23, "{upright}");
+        assert!(upright.x <= 3, "{upright}");
+
+        let double = drawn_size(&draw(StrokeText::new("l").height(40.0)));
+        assert!(double.y >= 40 && double.y <= 43, "{double}");
+
+        let rotated = drawn_size(&draw(StrokeText::new("l").height(20.0).rotation(FRAC_PI_2)));
+        assert_eq!(rotated, IVec2::new(upright.y, upright.x));
+    }
+
+    #[test]
+    fn missing_glyphs() {
+        let spaced = draw(StrokeText::new("l l"));
+        let missing = draw(StrokeText::new("l\u{e9}l"));
+        let size = spaced.size().as_ivec2();
+        for y in 0..size.y {
+            for x in 0..size.x {
+                let pos = IVec2::new(x, y);
+                assert_eq!(spaced.at(pos), missing.at(pos), "{pos}");
+            }
+        }
+        assert!(drawn_size(&missing).x > 5);
+    }
+}
@@ -0,0 +1,151 @@
+use glam::{Affine2, IVec2, Mat2, Vec2};
+
+use crate::camera::Camera;
+
+use super::SimpleCanvas;
+
+/// The transform stack and camera of a canvas.
+#[derive(Debug, Clone, Default)]
+pub(super) struct Transforms {
+    current: Affine2,
+    stack: Vec<Affine2>,
+    camera: Option<Camera>,
+}
+
+impl SimpleCanvas {
+    /// The transform from the coordinates given to drawing calls to canvas
+    /// pixels, including the camera.
+    pub(super) fn full_transform(&self) -> Affine2 {
+        match &self.transforms.camera {
+            Some(camera) => camera.transform(self.size()) * self.transforms.current,
+            None => self.transforms.current,
+        }
+    }
+
+    /// Maps a position given to a drawing call onto the nearest pixel.
+    pub(super) fn transform_pixel(&self, pos: IVec2) -> IVec2 {
+        self.full_transform()
+            .transform_point2(pos.as_vec2())
+            .round()
+            .as_ivec2()
+    }
+
+    pub fn push_transform(&mut self) {
+        self.transforms.stack.push(self.transforms.current);
+    }
+
+    pub fn pop_transform(&mut self) {
+        self.transforms.current = self.transforms.stack.pop().unwrap_or_default();
+    }
+
+    pub fn reset_transform(&mut self) {
+        self.transforms.current = Affine2::IDENTITY;
+        self.transforms.stack.clear();
+    }
+
+    pub fn transform(&self) -> Affine2 {
+        self.transforms.current
+    }
+
+    pub fn apply_transform(&mut self, transform: Affine2) {
+        self.transforms.current *= transform;
+    }
+
+    pub fn translate(&mut self, offset: Vec2) {
+        self.apply_transform(Affine2::from_translation(offset));
+    }
+
+    pub fn rotate(&mut self, radians: f32) {
+        self.apply_transform(Affine2::from_angle(radians));
+    }
+
+    pub fn scale(&mut self, scale: Vec2) {
+        self.apply_transform(Affine2::from_scale(scale));
+    }
+
+    pub fn shear(&mut self, shear: Vec2) {
+        let matrix = Mat2::from_cols(Vec2::new(1.0, shear.y), Vec2::new(shear.x, 1.0));
+        self.apply_transform(Affine2::from_mat2(matrix));
+    }
+
+    pub fn set_camera(&mut self, camera: Option<Camera>) {
+        self.transforms.camera = camera;
+    }
+
+    pub fn camera(&self) -> Option<&Camera> {
+        self.transforms.camera.as_ref()
+    }
+
+    pub fn camera_mut(&mut self) -> Option<&mut Camera> {
+        self.transforms.camera.as_mut()
+    }
+
+    /// Maps a position given to drawing calls onto canvas pixels.
+    pub fn world_to_screen(&self, pos: Vec2) -> Vec2 {
+        self.full_transform().transform_point2(pos)
+    }
+
+    /// Maps a canvas pixel position back to the coordinates drawing calls
+    /// take, such as to find what the mouse is over.
+    pub fn screen_to_world(&self, pos: Vec2) -> Vec2 {
+        self.full_transform().inverse().transform_point2(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use glam::U16Vec2;
+
+    use super::*;
+
+    #[test]
+    fn stack() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(10, 5));
+        canvas.translate(Vec2::new(5.0, 0.0));
+        canvas.push_transform();
+        canvas.scale(Vec2::splat(2.0));
+        assert_eq!(canvas.world_to_screen(Vec2::ONE), Vec2::new(7.0, 2.0));
+
+        canvas.pop_transform();
+        assert_eq!(canvas.world_to_screen(Vec2::ONE), Vec2::new(6.0, 1.0));
+
+        // popping past the bottom of the stack leaves no transform
+        canvas.pop_transform();
+        assert_eq!(canvas.transform(), Affine2::IDENTITY);
+    }
+
+    #[test]
+    fn order() {
+        // later transforms apply to the coordinates first
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(10, 5));
+        canvas.translate(Vec2::new(5.0, 5.0));
+        canvas.rotate(FRAC_PI_2);
+        let screen = canvas.world_to_screen(Vec2::new(1.0, 0.0));
+        assert!(screen.abs_diff_eq(Vec2::new(5.0, 6.0), 1e-5));
+
+        canvas.reset_transform();
+        canvas.shear(Vec2::new(1.0, 0.0));
+        assert_eq!(
+            canvas.world_to_screen(Vec2::new(1.0, 2.0)),
+            Vec2::new(3.0, 2.0)
+        );
+    }
+
+    #[test]
+    fn camera_under_stack() {
+        let mut canvas = SimpleCanvas::headless(U16Vec2::new(11, 5));
+        canvas.set_camera(Some(Camera {
+            zoom: 2.0,
+            ..Camera::looking_at(Vec2::new(10.0, 10.0))
+        }));
+        canvas.translate(Vec2::new(1.0, 0.0));
+        assert_eq!(
+            canvas.world_to_screen(Vec2::new(10.0, 10.0)),
+            Vec2::new(7.0, 4.5)
+        );
+        let world = canvas.screen_to_world(Vec2::new(7.0, 4.5));
+        assert!(world.abs_diff_eq(Vec2::new(10.0, 10.0), 1e-5));
+    }
+}
@@ -0,0 +1,173 @@
+use glam::{IVec2, Vec2, Vec3};
+use rgb::Rgb;
+
+use crate::style::Triangle;
+
+use super::SimpleCanvas;
+
+/// Returns the pixels whose centres are inside a triangle, limited to
+/// `min..max`, with the barycentric weight of each corner.
+///
+/// Pixels exactly on an edge are only included for top and left edges, so
+/// that two triangles sharing an edge never both cover a pixel.
+pub(crate) fn triangle_pixels(points: [Vec2; 3], min: IVec2, max: IVec2) -> Vec<(IVec2, Vec3)> {
+    let [a, b, c] = points;
+    let area = (b - a).perp_dot(c - a);
+    if area.abs() <= f32::EPSILON {
+        return vec![];
+    }
+    // wind clockwise on the canvas so inside is positive for every edge
+    let (b, c, swapped) = if area < 0.0 {
+        (c, b, true)
+    } else {
+        (b, c, false)
+    };
+    let area = area.abs();
+
+    let edges = [(b, c), (c, a), (a, b)];
+    let top_left = edges.map(|(from, to)| {
+        let top = from.y == to.y && to.x > from.x;
+        let left = to.y < from.y;
+        top || left
+    });
+
+    let lower = a.min(b).min(c).floor().as_ivec2().max(min);
+    let upper = (a.max(b).max(c).ceil().as_ivec2() + IVec2::ONE).min(max);
+
+    let mut pixels = vec![];
+    for y in lower.y..upper.y {
+        for x in lower.x..upper.x {
+            let pos = IVec2::new(x, y);
+            let p = pos.as_vec2();
+            let weights = edges.map(|(from, to)| (to - from).perp_dot(p - from));
+            let inside = weights
+                .iter()
+                .zip(top_left)
+                .all(|(weight, top_left)| *weight > 0.0 || (*weight == 0.0 && top_left));
+            if inside {
+                let mut weights = Vec3::from(weights) / area;
+                if swapped {
+                    weights = Vec3::new(weights.x, weights.z, weights.y);
+                }
+                pixels.push((pos, weights));
+            }
+        }
+    }
+    pixels
+}
+
+impl SimpleCanvas {
+    pub(super) fn draw_triangle(&mut self, triangle: Triangle) {
+        let transform = self.full_transform();
+        let [a, b, c] = triangle.vertices;
+        let points = [a.pos, b.pos, c.pos].map(|pos| transform.transform_point2(pos));
+        let size = self.size().as_ivec2();
+        if triangle.depth_test {
+            self.prepare_depth_buffer();
+        }
+
+        for (pos, weights) in triangle_pixels(points, IVec2::ZERO, size) {
+            if triangle.depth_test {
+                let depth = Vec3::new(a.depth, b.depth, c.depth).dot(weights);
+                let index = (pos.y * size.x + pos.x) as usize;
+                if depth >= self.depth[index] || !self.is_visible(pos.as_u16vec2()) {
+                    continue;
+                }
+                self.depth[index] = depth;
+            }
+
+            let channel = |channel: fn(&Rgb<u8>) -> u8| {
+                let values = Vec3::new(
+                    channel(&a.color) as f32,
+                    channel(&b.color) as f32,
+                    channel(&c.color) as f32,
+                );
+                values.dot(weights).round().clamp(0.0, 255.0) as u8
+            };
+            let color = crossterm::style::Color::Rgb {
+                r: channel(|color| color.r),
+                g: channel(|color| color.g),
+                b: channel(|color| color.b),
+            };
+            self.draw(pos.as_u16vec2(), Some(color));
+        }
+    }
+
+    /// Makes sure there is a depth value for every pixel, clearing the
+    /// buffer if the canvas has been resized.
+    fn prepare_depth_buffer(&mut self) {
+        let size = self.size();
+        let len = size.x as usize * size.y as usize;
+        if self.depth.len() != len {
+            self.depth = vec![f32::INFINITY; len];
+        }
+    }
+
+    pub fn clear_depth(&mut self) {
+        self.depth.fill(f32::INFINITY);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    const BOUNDS: (IVec2, IVec2) = (IVec2::ZERO, IVec2::splat(100));
+
+    fn covered(points: [Vec2; 3]) -> Vec<IVec2> {
+        triangle_pixels(points, BOUNDS.0, BOUNDS.1)
+            .into_iter()
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    #[test]
+    fn shared_edges() {
+        // a square split along its diagonal, with corners on pixel centres
+        let (a, b, c, d) = (
+            Vec2::new(2.0, 2.0),
+            Vec2::new(10.0, 2.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(2.0, 10.0),
+        );
+        let first = covered([a, b, c]);
+        let second = covered([a, c, d]);
+        let first_set: HashSet<_> = first.iter().copied().collect();
+        assert!(second.iter().all(|pos| !first_set.contains(pos)));
+        // top and left edges are included, bottom and right ones are not
+        assert_eq!(first.len() + second.len(), 64);
+        assert!(first_set.contains(&IVec2::new(2, 2)));
+        assert!(!first_set.contains(&IVec2::new(10, 5)));
+
+        // winding does not matter
+        let reversed: HashSet<_> = covered([c, b, a]).into_iter().collect();
+        assert_eq!(reversed, first_set);
+    }
+
+    #[test]
+    fn weights() {
+        let (a, b, c) = (Vec2::ZERO, Vec2::new(30.0, 0.0), Vec2::new(0.0, 30.0));
+        let weights_at = |points, pos| {
+            triangle_pixels(points, BOUNDS.0, BOUNDS.1)
+                .into_iter()
+                .find(|(pixel, _)| *pixel == pos)
+                .map(|(_, weights)| weights)
+                .unwrap()
+        };
+
+        let expected = Vec3::new(0.7, 0.1, 0.2);
+        let weights = weights_at([a, b, c], IVec2::new(3, 6));
+        assert!(weights.abs_diff_eq(expected, 1e-5));
+        // weights stay with their corners when the winding is reversed
+        let weights = weights_at([a, c, b], IVec2::new(3, 6));
+        assert!(weights.abs_diff_eq(Vec3::new(0.7, 0.2, 0.1), 1e-5));
+    }
+
+    #[test]
+    fn degenerate() {
+        let line = [Vec2::ZERO, Vec2::ONE, Vec2::splat(2.0)];
+        assert!(covered(line).is_empty());
+    }
+}
@@ -0,0 +1,193 @@
+use std::{collections::HashMap, fmt, sync::OnceLock};
+
+use glam::Vec2;
+
+/// Hershey font data for the built-in font, covering printable ASCII.
+const BUILTIN_JHF: &str = include_str!("simplex.jhf");
+
+/// The Hershey coordinate that all glyph coordinates are relative to.
+const ORIGIN: u8 = b'R';
+
+/// The baseline of a Hershey glyph in font units.
+pub const BASELINE: f32 = 9.0;
+
+/// The cap height of a Hershey glyph in font units.
+pub const CAP_HEIGHT: f32 = 21.0;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FontError {
+    /// A glyph header could not be read on the given line.
+    InvalidHeader(usize),
+    /// A glyph on the given line ended before all of its vertices were read.
+    UnexpectedEnd(usize),
+}
+
+impl fmt::Display for FontError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidHeader(line) => write!(f, "invalid glyph header on line {line}"),
+            Self::UnexpectedEnd(line) => write!(f, "glyph on line {line} ended early"),
+        }
+    }
+}
+
+impl std::error::Error for FontError {}
+
+/// A single glyph made of pen strokes.
+///
+/// Coordinates are in font units, with `x` relative to the glyph centre and `y`
+/// growing downwards with the baseline at [`BASELINE`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Glyph {
+    /// The left side bearing.
+    pub left: f32,
+    /// The right side bearing.
+    pub right: f32,
+    /// The polylines that make up the glyph.
+    pub strokes: Vec<Vec<Vec2>>,
+}
+
+impl Glyph {
+    /// The horizontal distance to the next glyph in font units.
+    pub fn advance(&self) -> f32 {
+        self.right - self.left
+    }
+}
+
+/// A vector stroke font in the Hershey format.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HersheyFont {
+    glyphs: HashMap<char, Glyph>,
+}
+
+impl HersheyFont {
+    /// Returns the font that ships with clod.
+    pub fn builtin() -> &'static HersheyFont {
+        static BUILTIN: OnceLock<HersheyFont> = OnceLock::new();
+        BUILTIN.get_or_init(|| {
+            Self::from_jhf(BUILTIN_JHF).expect("built-in font data should be valid")
+        })
+    }
+
+    /// Parses a font from the `.jhf` format.
+    ///
+    /// Glyphs are assigned to consecutive characters starting from the space
+    /// character, which is how the ASCII Hershey fonts are laid out. Glyphs
+    /// may be wrapped across several lines.
+    pub fn from_jhf(data: &str) -> Result<Self, FontError> {
+        let mut glyphs = HashMap::new();
+        let mut lines = data
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty());
+        let mut next_char = ' ';
+
+        while let Some((line_no, line)) = lines.next() {
+            let line_no = line_no + 1;
+            let vertex_count: usize = line
+                .get(5..8)
+                .and_then(|count| count.trim().parse().ok())
+                .ok_or(FontError::InvalidHeader(line_no))?;
+            if vertex_count == 0 {
+                return Err(FontError::InvalidHeader(line_no));
+            }
+
+            let mut data: Vec<u8> = line.as_bytes().get(8..).unwrap_or_default().to_vec();
+            while data.len() < vertex_count * 2 {
+                match lines.next() {
+                    Some((_, next)) => data.extend_from_slice(next.as_bytes()),
+                    None => return Err(FontError::UnexpectedEnd(line_no)),
+                }
+            }
+
+            let coordinate = |c: u8| (c as i16 - ORIGIN as i16) as f32;
+            let mut glyph = Glyph {
+                left: coordinate(data[0]),
+                right: coordinate(data[1]),
+                strokes: vec![],
+            };
+            let mut stroke = vec![];
+            for pair in data[2..vertex_count * 2].chunks(2) {
+                if pair == b" R" {
+                    glyph.strokes.push(std::mem::take(&mut stroke));
+                } else {
+                    stroke.push(Vec2::new(coordinate(pair[0]), coordinate(pair[1])));
+                }
+            }
+            glyph.strokes.push(stroke);
+            glyph.strokes.retain(|stroke| !stroke.is_empty());
+
+            glyphs.insert(next_char, glyph);
+            next_char = char::from_u32(next_char as u32 + 1).unwrap_or(char::REPLACEMENT_CHARACTER);
+        }
+
+        Ok(Self { glyphs })
+    }
+
+    /// Returns the glyph for a character, if the font has one.
+    pub fn glyph(&self, c: char) -> Option<&Glyph> {
+        self.glyphs.get(&c)
+    }
+
+    /// The horizontal distance from a character to the next in font units.
+    ///
+    /// Characters without a glyph take up as much room as a space, so the
+    /// text around them keeps its spacing.
+    pub fn advance(&self, c: char) -> f32 {
+        self.glyph(c)
+            .or_else(|| self.glyph(' '))
+            .map_or(0.0, Glyph::advance)
+    }
+
+    /// The width of a string in font units.
+    pub fn text_width(&self, text: &str) -> f32 {
+        text.chars().map(|c| self.advance(c)).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_covers_printable_ascii() {
+        let font = HersheyFont::builtin();
+        for c in ' '..='~' {
+            assert!(font.glyph(c).is_some(), "missing glyph for {c:?}");
+        }
+        assert!(font.glyph(' ').unwrap().strokes.is_empty());
+        assert!(font.glyph('\u{7f}').is_none());
+    }
+
+    #[test]
+    fn parse_jhf() {
+        // the letter A from the Hershey simplex font, wrapped over two lines
+        let font = HersheyFont::from_jhf("  501  9G]RFJ[ RRFZ[\n RMTWT\n").unwrap();
+        let glyph = font.glyph(' ').unwrap();
+        assert_eq!(glyph.left, -11.0);
+        assert_eq!(glyph.right, 11.0);
+        assert_eq!(glyph.advance(), 22.0);
+        assert_eq!(
+            glyph.strokes,
+            vec![
+                vec![Vec2::new(0.0, -12.0), Vec2::new(-8.0, 9.0)],
+                vec![Vec2::new(0.0, -12.0), Vec2::new(8.0, 9.0)],
+                vec![Vec2::new(-5.0, 2.0), Vec2::new(5.0, 2.0)],
+            ]
+        );
+        // the font has no x, which takes up as much room as a space
+        assert_eq!(font.text_width("  x"), 66.0);
+    }
+
+    #[test]
+    fn parse_jhf_errors() {
+        assert_eq!(
+            HersheyFont::from_jhf("  501"),
+            Err(FontError::InvalidHeader(1))
+        );
+        assert_eq!(
+            HersheyFont::from_jhf("  501  9G]RFJ[ RRFZ["),
+            Err(FontError::UnexpectedEnd(1))
+        );
+    }
+}
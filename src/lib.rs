@@ -1,37 +1,76 @@
-use std::{
-    io,
-    time::{Duration, Instant},
-};
+use std::{io, time::Instant};
 
+use camera::{Camera, PerspectiveCamera};
+use clip::{Clip, Mask};
+use config::AppConfig;
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
+    event::{self, Event, KeyEvent, MouseEvent},
     style::Color,
 };
 use engine::SimpleCanvas;
-use glam::{IVec2, U16Vec2, Vec2};
+use glam::{Affine2, IVec2, Mat4, U16Vec2, UVec2, Vec2};
+use mesh::Mesh;
+use palette::{ColorMode, Dither, Palette};
 use rgb::Rgb;
-use style::{Circle, StyledPrint};
+use style::{
+    Arc, Ascii, BlendMode, CanvasAlignment, Circle, Ellipse, FloodFill, MeshStyle, Path, Polygon,
+    Rect, Stroke, StrokeText, StyledPrint, Stylize, Triangle,
+};
+use timestep::FixedTimestep;
 
+pub mod camera;
+pub mod clip;
+pub mod color;
+pub mod config;
 mod engine;
+pub mod font;
+pub mod mesh;
+pub mod mouse;
+pub mod palette;
 pub mod style;
+mod timestep;
 
 pub struct State {
     canvas: SimpleCanvas,
     quit: bool,
     dt_s: f32,
+    fixed_dt_s: f32,
+    alpha: f32,
+    /// The size of a cell in screen pixels, when the mouse is reported in
+    /// screen pixels.
+    mouse_cell_size: Option<UVec2>,
     elapsed_time_ms: u128,
 }
 
 impl State {
-    fn new() -> io::Result<Self> {
+    fn new(config: &AppConfig) -> io::Result<Self> {
+        let canvas = SimpleCanvas::new(config)?;
+        let mouse_cell_size = canvas.pixel_mouse().then(mouse::cell_size).flatten();
         Ok(Self {
-            canvas: SimpleCanvas::new()?,
+            canvas,
             quit: false,
             dt_s: 0.0,
+            fixed_dt_s: config.tick_time().as_secs_f32(),
+            alpha: 0.0,
+            mouse_cell_size,
             elapsed_time_ms: 0,
         })
     }
 
+    /// Creates a state of `size` cells that draws without a terminal, for
+    /// tests and benchmarks.
+    pub fn headless(size: U16Vec2) -> Self {
+        Self {
+            canvas: SimpleCanvas::headless(size),
+            quit: false,
+            dt_s: 0.0,
+            fixed_dt_s: AppConfig::default().tick_time().as_secs_f32(),
+            alpha: 0.0,
+            mouse_cell_size: None,
+            elapsed_time_ms: 0,
+        }
+    }
+
     pub fn exit(&mut self) {
         self.quit = true;
     }
@@ -40,6 +79,37 @@ impl State {
         self.dt_s
     }
 
+    /// Returns how many seconds every [`App::fixed_update`] moves the app
+    /// forward by.
+    pub fn fixed_delta_seconds(&self) -> f32 {
+        self.fixed_dt_s
+    }
+
+    /// Returns how far the time is between the last fixed update and the
+    /// next, from `0.0` to `1.0`.
+    ///
+    /// Drawing things between their positions at the last two fixed updates
+    /// by this much keeps them moving smoothly when frames and ticks do not
+    /// line up.
+    pub fn interpolation_alpha(&self) -> f32 {
+        self.alpha
+    }
+
+    /// Returns the canvas pixel under the mouse, in the same coordinates as
+    /// [`canvas_size`](Self::canvas_size).
+    ///
+    /// Unless [`AppConfig::pixel_mouse`] is turned on, the terminal only
+    /// reports which cell the mouse is in, so this is the top of the two
+    /// pixels in that cell.
+    pub fn mouse_pixel(&self, event: &MouseEvent) -> IVec2 {
+        match self.mouse_cell_size {
+            Some(cell_size) => {
+                mouse::screen_to_pixel(UVec2::new(event.column as u32, event.row as u32), cell_size)
+            }
+            None => mouse::cell_to_pixel(event.column, event.row),
+        }
+    }
+
     pub fn elapsed_millis(&self) -> u128 {
         self.elapsed_time_ms
     }
@@ -52,6 +122,178 @@ impl State {
         self.canvas.set_background_color(color);
     }
 
+    /// Sets the RGB values used for named and 256 color palette colors when
+    /// anti-aliased edges blend with them.
+    ///
+    /// Terminals let users choose these colors, so this can be set to match
+    /// the terminal theme. Defaults to [`Palette::xterm`].
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.canvas.set_palette(palette);
+    }
+
+    pub fn palette(&self) -> &Palette {
+        self.canvas.palette()
+    }
+
+    /// Sets how drawing combines with what is already on the canvas, such as
+    /// adding for glowing particles or multiplying for shadows.
+    ///
+    /// The mode applies to every drawing call until it is changed, except
+    /// [`print`](Self::print) and [`erase`](Self::erase), and is reset to
+    /// [`BlendMode::Normal`] at the start of every frame.
+    pub fn set_blend_mode(&mut self, mode: BlendMode) {
+        self.canvas.set_blend_mode(mode);
+    }
+
+    pub fn blend_mode(&self) -> BlendMode {
+        self.canvas.blend_mode()
+    }
+
+    /// Sets whether anti-aliased edges are blended in linear light, which
+    /// keeps thin lines and the edges of shapes as bright as they should be.
+    ///
+    /// Turned on by default. When turned off, the sRGB values are blended
+    /// directly, which makes edges look darker.
+    pub fn set_linear_blending(&mut self, linear: bool) {
+        self.canvas.set_linear_blending(linear);
+    }
+
+    pub fn linear_blending(&self) -> bool {
+        self.canvas.linear_blending()
+    }
+
+    /// Sets how many colors the terminal can show. Every color on the canvas
+    /// is reduced to the nearest one the terminal has when the frame is
+    /// rendered.
+    ///
+    /// Defaults to [`ColorMode::TrueColor`], which leaves colors as they are.
+    pub fn set_color_mode(&mut self, mode: ColorMode) {
+        self.canvas.set_color_mode(mode);
+    }
+
+    pub fn color_mode(&self) -> ColorMode {
+        self.canvas.color_mode()
+    }
+
+    /// Sets how pixels are dithered when colors are reduced by the
+    /// [`ColorMode`], which hides banding in gradients and shading. Text is
+    /// never dithered.
+    pub fn set_dither(&mut self, dither: Dither) {
+        self.canvas.set_dither(dither);
+    }
+
+    pub fn dither(&self) -> Dither {
+        self.canvas.dither()
+    }
+
+    /// Renders the canvas with the plain ASCII characters of `ascii` instead
+    /// of half blocks, or with half blocks again when `None`.
+    ///
+    /// Useful for terminals, serial consoles and CI logs that cannot show
+    /// block characters. Drawing works as before, only the rendered frame
+    /// changes.
+    pub fn set_ascii(&mut self, ascii: Option<Ascii>) {
+        self.canvas.set_ascii(ascii);
+    }
+
+    pub fn ascii(&self) -> Option<&Ascii> {
+        self.canvas.ascii()
+    }
+
+    /// Draws with `mode` inside `draw`, then restores the previous mode.
+    pub fn with_blend_mode(&mut self, mode: BlendMode, draw: impl FnOnce(&mut Self)) {
+        let previous = self.blend_mode();
+        self.set_blend_mode(mode);
+        draw(self);
+        self.set_blend_mode(previous);
+    }
+
+    /// Saves the current transform, to be restored by
+    /// [`pop_transform`](Self::pop_transform).
+    ///
+    /// The transform applies to every drawing call except
+    /// [`print`](Self::print), and is reset at the start of every frame.
+    pub fn push_transform(&mut self) {
+        self.canvas.push_transform();
+    }
+
+    pub fn pop_transform(&mut self) {
+        self.canvas.pop_transform();
+    }
+
+    pub fn reset_transform(&mut self) {
+        self.canvas.reset_transform();
+    }
+
+    pub fn transform(&self) -> Affine2 {
+        self.canvas.transform()
+    }
+
+    pub fn apply_transform(&mut self, transform: Affine2) {
+        self.canvas.apply_transform(transform);
+    }
+
+    pub fn translate(&mut self, offset: Vec2) {
+        self.canvas.translate(offset);
+    }
+
+    pub fn rotate(&mut self, radians: f32) {
+        self.canvas.rotate(radians);
+    }
+
+    pub fn scale(&mut self, scale: Vec2) {
+        self.canvas.scale(scale);
+    }
+
+    pub fn shear(&mut self, shear: Vec2) {
+        self.canvas.shear(shear);
+    }
+
+    pub fn set_camera(&mut self, camera: Option<Camera>) {
+        self.canvas.set_camera(camera);
+    }
+
+    pub fn camera(&self) -> Option<&Camera> {
+        self.canvas.camera()
+    }
+
+    pub fn camera_mut(&mut self) -> Option<&mut Camera> {
+        self.canvas.camera_mut()
+    }
+
+    /// Maps a position given to drawing calls onto canvas pixels, through
+    /// the camera and the transform stack.
+    pub fn world_to_screen(&self, pos: Vec2) -> Vec2 {
+        self.canvas.world_to_screen(pos)
+    }
+
+    pub fn screen_to_world(&self, pos: Vec2) -> Vec2 {
+        self.canvas.screen_to_world(pos)
+    }
+
+    /// Restricts drawing, including printed text, to a region of the
+    /// canvas until it is popped.
+    ///
+    /// Regions on the stack intersect, and the stack is cleared at the
+    /// start of every frame.
+    pub fn push_clip(&mut self, clip: impl Into<Clip>) {
+        self.canvas.push_clip(clip);
+    }
+
+    pub fn pop_clip(&mut self) -> Option<Clip> {
+        self.canvas.pop_clip()
+    }
+
+    pub fn reset_clip(&mut self) {
+        self.canvas.reset_clip();
+    }
+
+    /// Returns a mask of every pixel drawn so far, to clip later drawing to
+    /// the same shape.
+    pub fn drawn_mask(&self) -> Mask {
+        self.canvas.drawn_mask()
+    }
+
     pub fn point(&mut self, pos: IVec2) {
         self.canvas.point(pos);
     }
@@ -72,6 +314,75 @@ impl State {
         self.canvas.aa_circle(pos, circle);
     }
 
+    pub fn ellipse(&mut self, pos: Vec2, ellipse: Ellipse) {
+        self.canvas.ellipse(pos, ellipse);
+    }
+
+    pub fn arc(&mut self, pos: Vec2, arc: Arc) {
+        self.canvas.arc(pos, arc);
+    }
+
+    pub fn rect(&mut self, pos: Vec2, rect: Rect) {
+        self.canvas.rect(pos, rect);
+    }
+
+    pub fn fill_rect(&mut self, pos: Vec2, rect: Rect) {
+        self.canvas.fill_rect(pos, rect);
+    }
+
+    /// Draws `rect` with its corners rounded by `radius`, a shorthand for
+    /// [`rect`](Self::rect) with [`RectLike::corner_radius`](style::RectLike::corner_radius).
+    pub fn rounded_rect(&mut self, pos: Vec2, radius: f32, rect: Rect) {
+        self.canvas.rounded_rect(pos, radius, rect);
+    }
+
+    pub fn stroke_circle(&mut self, pos: Vec2, radius: f32, stroke: Stroke) {
+        self.canvas.stroke_circle(pos, radius, stroke);
+    }
+
+    /// Strokes the outline of a rectangle of `size` with corners rounded by
+    /// `corner_radius`, placed like [`rect`](Self::rect).
+    pub fn stroke_rect(&mut self, pos: Vec2, size: Vec2, corner_radius: f32, stroke: Stroke) {
+        self.canvas.stroke_rect(pos, size, corner_radius, stroke);
+    }
+
+    pub fn polygon(&mut self, polygon: Polygon) {
+        self.canvas.polygon(polygon);
+    }
+
+    /// Fills and strokes a path, such as one parsed from SVG path data.
+    pub fn path(&mut self, path: &Path) {
+        self.canvas.path(path);
+    }
+
+    pub fn stroke_line(&mut self, start: Vec2, end: Vec2, stroke: Stroke) {
+        self.canvas.stroke_line(start, end, stroke);
+    }
+
+    pub fn polyline(&mut self, points: &[Vec2], stroke: Stroke) {
+        self.canvas.polyline(points, stroke);
+    }
+
+    pub fn quad_bezier(&mut self, start: Vec2, control: Vec2, end: Vec2, stroke: Stroke) {
+        self.canvas.quad_bezier(start, control, end, stroke);
+    }
+
+    pub fn cubic_bezier(
+        &mut self,
+        start: Vec2,
+        first: Vec2,
+        second: Vec2,
+        end: Vec2,
+        stroke: Stroke,
+    ) {
+        self.canvas.cubic_bezier(start, first, second, end, stroke);
+    }
+
+    /// Draws a smooth curve that passes through every point.
+    pub fn spline(&mut self, points: &[Vec2], stroke: Stroke) {
+        self.canvas.spline(points, stroke);
+    }
+
     pub fn aa_line(&mut self, start: Vec2, end: Vec2) {
         self.canvas.aa_line(start, end);
     }
@@ -80,6 +391,39 @@ impl State {
         self.canvas.aa_line_with_color(start, end, color);
     }
 
+    /// Fills the region of similar pixels connected to `pos`, like a paint
+    /// bucket.
+    pub fn flood_fill(&mut self, pos: IVec2, fill: FloodFill) {
+        self.canvas.flood_fill(pos, fill);
+    }
+
+    /// Fills a triangle, blending the colors of its corners.
+    ///
+    /// Triangles with depth testing only draw over pixels that are further
+    /// away, so meshes can be drawn in any order. The depth buffer is cleared
+    /// at the start of every frame.
+    pub fn triangle(&mut self, triangle: Triangle) {
+        self.canvas.triangle(triangle);
+    }
+
+    pub fn clear_depth(&mut self) {
+        self.canvas.clear_depth();
+    }
+
+    /// Draws a 3D mesh placed in the world by `model`, as seen by `camera`.
+    ///
+    /// Flat shaded meshes are depth tested, so several meshes drawn in the
+    /// same frame hide each other correctly.
+    pub fn mesh(
+        &mut self,
+        mesh: &Mesh,
+        model: Mat4,
+        camera: &PerspectiveCamera,
+        style: &MeshStyle,
+    ) {
+        self.canvas.mesh(mesh, model, camera, style);
+    }
+
     pub fn erase(&mut self, pos: IVec2) {
         self.canvas.erase(pos);
     }
@@ -88,6 +432,10 @@ impl State {
         self.canvas.print(content);
     }
 
+    pub fn stroke_text<'a>(&mut self, pos: Vec2, text: impl Into<StrokeText<'a>>) {
+        self.canvas.stroke_text(pos, text);
+    }
+
     pub fn at(&self, pos: IVec2) -> Option<Color> {
         self.canvas.at(pos)
     }
@@ -110,53 +458,116 @@ pub type AppResult = Result<(), AppError>;
 
 pub trait App {
     fn update(&mut self, state: &mut State) -> Result<(), String>;
-    fn init(&mut self, _state: &mut State) -> Result<(), String> { Ok(())}
+    fn init(&mut self, _state: &mut State) -> Result<(), String> {
+        Ok(())
+    }
+    /// Moves the app forward by [`State::fixed_delta_seconds`], called at
+    /// the tick rate of the [`AppConfig`] however fast frames are drawn.
+    /// Simulations and physics that should not depend on the frame rate
+    /// belong here, with drawing left to [`update`](Self::update). Never
+    /// called when [`AppConfig::tick_rate`] is zero or less.
+    fn fixed_update(&mut self, _state: &mut State) -> Result<(), String> {
+        Ok(())
+    }
     fn on_key_event(&mut self, _state: &mut State, _event: KeyEvent) {}
+    /// Called for clicks, drags, moves and scrolls of the mouse, when
+    /// [`AppConfig::mouse_capture`] is turned on. See
+    /// [`State::mouse_pixel`] for the pixel under the mouse.
+    fn on_mouse_event(&mut self, _state: &mut State, _event: MouseEvent) {}
+    /// Called when the terminal gains or loses focus, when
+    /// [`AppConfig::focus_events`] is turned on.
+    fn on_focus_changed(&mut self, _state: &mut State, _focused: bool) {}
+    /// Called with text pasted into the terminal, when
+    /// [`AppConfig::bracketed_paste`] is turned on. Otherwise pasted text
+    /// arrives as key events.
+    fn on_paste(&mut self, _state: &mut State, _text: String) {}
+    /// Called after the terminal is resized, with the old and new sizes of
+    /// the canvas in pixels.
+    fn on_resize(&mut self, _state: &mut State, _old: U16Vec2, _new: U16Vec2) {}
 
     fn run(&mut self) -> AppResult {
-        let mut state = State::new()?;
+        self.run_with(AppConfig::default())
+    }
+
+    /// Runs the app with `config` until it exits or a quit key is pressed.
+    fn run_with(&mut self, config: AppConfig) -> AppResult {
+        let mut state = State::new(&config)?;
+        if let Some(color) = config.background_color {
+            state.set_background_color(Some(color));
+        }
+        let frame_time = config.frame_time();
+        let mut timestep = FixedTimestep::new(config.tick_time(), config.max_ticks_per_frame);
         let global_timer = Instant::now();
         let mut timer = Instant::now();
         if let Err(err) = self.init(&mut state) {
             return Err(AppError::InitError(err));
         }
         while !state.quit {
-            let interval: u128 = 16;
             'poll_loop: loop {
-                let poll_duration = interval.saturating_sub(timer.elapsed().as_millis());
-                if event::poll(Duration::from_millis(poll_duration.try_into().unwrap()))? {
+                let poll_duration = frame_time.saturating_sub(timer.elapsed());
+                if event::poll(poll_duration)? {
                     let event = event::read()?;
                     match event {
-                        Event::FocusGained => todo!(),
-                        Event::FocusLost => todo!(),
+                        Event::FocusGained => self.on_focus_changed(&mut state, true),
+                        Event::FocusLost => self.on_focus_changed(&mut state, false),
                         Event::Key(key_event) => {
                             self.on_key_event(&mut state, key_event);
-                            match key_event.code {
-                                KeyCode::Char('q') => state.exit(),
-                                KeyCode::Esc => state.exit(),
-                                KeyCode::Char('c') => {
-                                    if let KeyModifiers::CONTROL = key_event.modifiers {
-                                        state.exit()
-                                    }
-                                }
-                                _ => continue,
-                            };
+                            if config.is_quit_key(&key_event) {
+                                state.exit();
+                            }
                         }
+                        Event::Mouse(mouse_event) => self.on_mouse_event(&mut state, mouse_event),
+                        Event::Paste(text) => self.on_paste(&mut state, text),
                         Event::Resize(columns, rows) => {
-                            state.canvas.resize(U16Vec2::new(columns, rows))
+                            let old = state.canvas_size();
+                            state.canvas.resize(U16Vec2::new(columns, rows));
+                            if state.mouse_cell_size.is_some() {
+                                // fonts can change size along with the terminal
+                                state.mouse_cell_size =
+                                    mouse::cell_size().or(state.mouse_cell_size);
+                            }
+                            let new = state.canvas_size();
+                            if old != new {
+                                self.on_resize(&mut state, old, new);
+                            }
                         }
-                        _ => continue,
                     }
                 }
-                if timer.elapsed().as_millis() > interval {
+                if timer.elapsed() >= frame_time {
                     break 'poll_loop;
                 }
             }
 
-            state.dt_s = timer.elapsed().as_secs_f32();
+            let elapsed = timer.elapsed();
+            state.dt_s = elapsed.as_secs_f32();
             state.elapsed_time_ms = global_timer.elapsed().as_millis();
             timer = Instant::now();
-            if let Err(err) = self.update(&mut state) {
+            let size = state.canvas.size() / U16Vec2::new(1, 2);
+            let too_small = config
+                .min_size
+                .filter(|min_size| size.cmplt(*min_size).any());
+            if too_small.is_some() {
+                // the app is paused, so there is nothing to catch up on after
+                timestep.reset();
+            } else {
+                for _ in 0..timestep.advance(elapsed) {
+                    if let Err(err) = self.fixed_update(&mut state) {
+                        return Err(AppError::UpdateError(err));
+                    }
+                }
+            }
+            state.alpha = timestep.alpha();
+            state.canvas.reset_transform();
+            state.canvas.reset_clip();
+            state.canvas.clear_depth();
+            state.canvas.set_blend_mode(BlendMode::Normal);
+            if let Some(min_size) = too_small {
+                let message = format!(
+                    "Please make the terminal at least {}x{}",
+                    min_size.x, min_size.y
+                );
+                state.print(message.as_str().align(CanvasAlignment::CENTER));
+            } else if let Err(err) = self.update(&mut state) {
                 return Err(AppError::UpdateError(err));
             }
             state.canvas.render()?;
@@ -167,7 +578,26 @@ pub trait App {
 
 #[cfg(test)]
 mod test {
+    use crossterm::event::{KeyModifiers, MouseEventKind};
+
+    use super::*;
 
     #[test]
     fn test_seed_from_u64() {}
+
+    #[test]
+    fn mouse_pixel() {
+        let mut state = State::headless(U16Vec2::new(20, 10));
+        let event = MouseEvent {
+            kind: MouseEventKind::Moved,
+            column: 17,
+            row: 41,
+            modifiers: KeyModifiers::NONE,
+        };
+        assert_eq!(state.mouse_pixel(&event), IVec2::new(17, 82));
+
+        // reported in screen pixels, in the bottom half of a cell
+        state.mouse_cell_size = Some(UVec2::new(8, 16));
+        assert_eq!(state.mouse_pixel(&event), IVec2::new(2, 5));
+    }
 }
@@ -0,0 +1,193 @@
+//! Triangle meshes for drawing in 3D, loaded from Wavefront OBJ files or
+//! built by hand.
+
+use std::{fmt, str::FromStr};
+
+use glam::Vec3;
+
+/// A 3D model made of triangles.
+///
+/// Faces wind anticlockwise when seen from the front, as in OBJ files.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Mesh {
+    pub positions: Vec<Vec3>,
+    /// The corners of each triangle, as indices into `positions`.
+    pub faces: Vec<[usize; 3]>,
+}
+
+impl Mesh {
+    pub fn new(positions: Vec<Vec3>, faces: Vec<[usize; 3]>) -> Self {
+        Self { positions, faces }
+    }
+
+    /// A cube centred on the origin, with sides of length 1.
+    pub fn cube() -> Self {
+        let positions = (0..8)
+            .map(|i| {
+                let corner = |bit: usize| if i & bit == 0 { -0.5 } else { 0.5 };
+                Vec3::new(corner(1), corner(2), corner(4))
+            })
+            .collect();
+        // each side is a quad listed anticlockwise from outside the cube
+        let quads = [
+            [0, 2, 3, 1],
+            [4, 5, 7, 6],
+            [0, 1, 5, 4],
+            [2, 6, 7, 3],
+            [0, 4, 6, 2],
+            [1, 3, 7, 5],
+        ];
+        let faces = quads
+            .iter()
+            .flat_map(|[a, b, c, d]| [[*a, *b, *c], [*a, *c, *d]])
+            .collect();
+        Self { positions, faces }
+    }
+
+    /// Reads a mesh from the text of a Wavefront OBJ file.
+    ///
+    /// Only vertex positions and faces are read. Faces with more than three
+    /// corners are split into triangles, and everything else, such as
+    /// normals, texture coordinates and materials, is ignored.
+    pub fn from_obj(data: &str) -> Result<Self, ObjError> {
+        let mut mesh = Self::default();
+        for (index, line) in data.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.split('#').next().unwrap_or_default();
+            let mut parts = line.split_whitespace();
+            match parts.next() {
+                Some("v") => {
+                    let mut coordinate = || {
+                        parts
+                            .next()
+                            .and_then(|part| part.parse().ok())
+                            .ok_or(ObjError::InvalidNumber(line_number))
+                    };
+                    let position = Vec3::new(coordinate()?, coordinate()?, coordinate()?);
+                    mesh.positions.push(position);
+                }
+                Some("f") => {
+                    let corners = parts
+                        .map(|part| mesh.position_index(part, line_number))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    if corners.len() < 3 {
+                        return Err(ObjError::TooFewCorners(line_number));
+                    }
+                    for i in 1..corners.len() - 1 {
+                        mesh.faces.push([corners[0], corners[i], corners[i + 1]]);
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(mesh)
+    }
+
+    /// Turns a face corner such as `3`, `3/1`, `3//2` or `-1` into an index
+    /// into the positions read so far.
+    fn position_index(&self, corner: &str, line_number: usize) -> Result<usize, ObjError> {
+        let index: isize = corner
+            .split('/')
+            .next()
+            .and_then(|index| index.parse().ok())
+            .ok_or(ObjError::InvalidNumber(line_number))?;
+        let count = self.positions.len() as isize;
+        let index = match index {
+            // negative indices count back from the last position
+            ..=-1 => count + index,
+            1.. => index - 1,
+            0 => return Err(ObjError::InvalidIndex(line_number)),
+        };
+        if (0..count).contains(&index) {
+            Ok(index as usize)
+        } else {
+            Err(ObjError::InvalidIndex(line_number))
+        }
+    }
+}
+
+impl FromStr for Mesh {
+    type Err = ObjError;
+
+    fn from_str(data: &str) -> Result<Self, Self::Err> {
+        Self::from_obj(data)
+    }
+}
+
+/// An error from reading an OBJ file, with the line number it was found on.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ObjError {
+    /// A coordinate or index could not be read.
+    InvalidNumber(usize),
+    /// A face refers to a vertex that does not exist.
+    InvalidIndex(usize),
+    /// A face has fewer than three corners.
+    TooFewCorners(usize),
+}
+
+impl fmt::Display for ObjError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidNumber(line) => write!(f, "invalid number on line {line}"),
+            Self::InvalidIndex(line) => write!(f, "invalid vertex index on line {line}"),
+            Self::TooFewCorners(line) => write!(f, "face with fewer than 3 corners on line {line}"),
+        }
+    }
+}
+
+impl std::error::Error for ObjError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn obj() {
+        let data = "
+            # a square and a triangle
+            o square
+            v 0 0 0
+            v 1 0 0
+            v 1 1 0
+            v 0 1 0.5 1.0
+            vn 0 0 1
+            f 1/1/1 2/2/1 3//1 4//1
+            f -1 -2 -3
+        ";
+        let mesh: Mesh = data.parse().unwrap();
+        assert_eq!(mesh.positions.len(), 4);
+        assert_eq!(mesh.positions[3], Vec3::new(0.0, 1.0, 0.5));
+        assert_eq!(mesh.faces, vec![[0, 1, 2], [0, 2, 3], [3, 2, 1]]);
+    }
+
+    #[test]
+    fn obj_errors() {
+        assert_eq!(Mesh::from_obj("v 0 0"), Err(ObjError::InvalidNumber(1)));
+        assert_eq!(Mesh::from_obj("v 0 x 0"), Err(ObjError::InvalidNumber(1)));
+        let two = "v 0 0 0\nv 1 0 0\n";
+        assert_eq!(
+            Mesh::from_obj(&format!("{two}f 1 2")),
+            Err(ObjError::TooFewCorners(3))
+        );
+        assert_eq!(
+            Mesh::from_obj(&format!("{two}f 1 2 3")),
+            Err(ObjError::InvalidIndex(3))
+        );
+        assert_eq!(
+            Mesh::from_obj(&format!("{two}f 0 1 2")),
+            Err(ObjError::InvalidIndex(3))
+        );
+    }
+
+    #[test]
+    fn cube_faces_outwards() {
+        let cube = Mesh::cube();
+        assert_eq!(cube.faces.len(), 12);
+        for [a, b, c] in cube.faces {
+            let [a, b, c] = [a, b, c].map(|i| cube.positions[i]);
+            let normal = (b - a).cross(c - a);
+            let center = (a + b + c) / 3.0;
+            assert!(normal.dot(center) > 0.0);
+        }
+    }
+}
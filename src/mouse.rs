@@ -0,0 +1,69 @@
+//! Converts the positions of mouse events into canvas pixels.
+//!
+//! Terminals report the mouse in cells, and every cell holds two pixels of
+//! the canvas, one above the other. Reports in cells can only point at the
+//! top pixel of a cell. Terminals that report the mouse in screen pixels
+//! (SGR-pixel mode, turned on with
+//! [`AppConfig::pixel_mouse`](crate::config::AppConfig::pixel_mouse)) also
+//! say which half of the cell it is in, see [`screen_to_pixel`].
+
+use crossterm::terminal;
+use glam::{IVec2, UVec2};
+
+/// Returns the top pixel of the cell at `column` and `row`.
+pub fn cell_to_pixel(column: u16, row: u16) -> IVec2 {
+    IVec2::new(column as i32, row as i32 * 2)
+}
+
+/// Returns the cell holding `pixel`.
+pub fn pixel_to_cell(pixel: IVec2) -> IVec2 {
+    IVec2::new(pixel.x, pixel.y.div_euclid(2))
+}
+
+/// Returns the canvas pixel under a position in screen pixels, given the
+/// size of a cell in screen pixels.
+///
+/// The size of a cell comes from [`cell_size`].
+pub fn screen_to_pixel(pos: UVec2, cell_size: UVec2) -> IVec2 {
+    let cell_size = cell_size.max(UVec2::ONE);
+    IVec2::new(
+        (pos.x / cell_size.x) as i32,
+        (pos.y * 2 / cell_size.y) as i32,
+    )
+}
+
+/// Returns the size of a cell in screen pixels, or `None` when the terminal
+/// does not say how big it is in pixels.
+pub fn cell_size() -> Option<UVec2> {
+    let size = terminal::window_size().ok()?;
+    let cells = UVec2::new(size.columns as u32, size.rows as u32);
+    let pixels = UVec2::new(size.width as u32, size.height as u32);
+    (cells.min_element() > 0 && pixels.min_element() > 0).then(|| pixels / cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cells() {
+        assert_eq!(cell_to_pixel(3, 4), IVec2::new(3, 8));
+        assert_eq!(pixel_to_cell(IVec2::new(3, 8)), IVec2::new(3, 4));
+        assert_eq!(pixel_to_cell(IVec2::new(3, 9)), IVec2::new(3, 4));
+        assert_eq!(pixel_to_cell(IVec2::new(0, -1)), IVec2::new(0, -1));
+    }
+
+    #[test]
+    fn screen_pixels() {
+        let cell_size = UVec2::new(8, 16);
+        assert_eq!(
+            screen_to_pixel(UVec2::new(17, 35), cell_size),
+            IVec2::new(2, 4)
+        );
+        // the bottom half of the same cell
+        assert_eq!(
+            screen_to_pixel(UVec2::new(17, 41), cell_size),
+            IVec2::new(2, 5)
+        );
+    }
+}
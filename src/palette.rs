@@ -0,0 +1,246 @@
+//! Maps terminal colors to RGB, so anti-aliased edges can blend with colors
+//! however they were given.
+
+use crossterm::style::Color;
+use rgb::Rgb;
+
+/// How many colors the terminal can show.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum ColorMode {
+    /// Any RGB color.
+    #[default]
+    TrueColor,
+    /// The 256 color palette.
+    Ansi256,
+    /// The 16 basic ANSI colors.
+    Ansi16,
+}
+
+/// How colors are spread across neighbouring pixels when they are reduced
+/// to a [`ColorMode`] with fewer colors, so that gradients do not band.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Dither {
+    /// Every pixel takes the nearest color.
+    #[default]
+    None,
+    /// The error of each pixel is passed on to the pixels right of and below
+    /// it. Smooth, but patterns can crawl in animations.
+    FloydSteinberg,
+    /// Like Floyd–Steinberg, but only passing on three quarters of the
+    /// error, for more contrast.
+    Atkinson,
+    /// A fixed 4x4 threshold pattern, which stays still in animations.
+    Bayer,
+}
+
+/// The RGB values of the 16 basic ANSI colors.
+///
+/// Terminals let users pick these colors, so the palette only guesses what
+/// they look like. The rest of the 256 color palette is the same everywhere.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Palette {
+    /// The colors from black, red, green, yellow, blue, magenta, cyan and
+    /// white, followed by their bright versions.
+    pub ansi: [Rgb<u8>; 16],
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::xterm()
+    }
+}
+
+impl Palette {
+    /// The default colors of xterm.
+    pub const fn xterm() -> Self {
+        Self {
+            ansi: [
+                Rgb::new(0, 0, 0),
+                Rgb::new(205, 0, 0),
+                Rgb::new(0, 205, 0),
+                Rgb::new(205, 205, 0),
+                Rgb::new(0, 0, 238),
+                Rgb::new(205, 0, 205),
+                Rgb::new(0, 205, 205),
+                Rgb::new(229, 229, 229),
+                Rgb::new(127, 127, 127),
+                Rgb::new(255, 0, 0),
+                Rgb::new(0, 255, 0),
+                Rgb::new(255, 255, 0),
+                Rgb::new(92, 92, 255),
+                Rgb::new(255, 0, 255),
+                Rgb::new(0, 255, 255),
+                Rgb::new(255, 255, 255),
+            ],
+        }
+    }
+
+    /// The colors of the VGA text mode, as used by the Linux console.
+    pub const fn vga() -> Self {
+        Self {
+            ansi: [
+                Rgb::new(0, 0, 0),
+                Rgb::new(170, 0, 0),
+                Rgb::new(0, 170, 0),
+                Rgb::new(170, 85, 0),
+                Rgb::new(0, 0, 170),
+                Rgb::new(170, 0, 170),
+                Rgb::new(0, 170, 170),
+                Rgb::new(170, 170, 170),
+                Rgb::new(85, 85, 85),
+                Rgb::new(255, 85, 85),
+                Rgb::new(85, 255, 85),
+                Rgb::new(255, 255, 85),
+                Rgb::new(85, 85, 255),
+                Rgb::new(255, 85, 255),
+                Rgb::new(85, 255, 255),
+                Rgb::new(255, 255, 255),
+            ],
+        }
+    }
+
+    /// Returns the RGB value of a color from the 256 color palette.
+    pub fn ansi_value(&self, index: u8) -> Rgb<u8> {
+        match index {
+            0..=15 => self.ansi[index as usize],
+            16..=231 => {
+                // a 6x6x6 color cube
+                let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+                let index = index - 16;
+                Rgb::new(level(index / 36), level(index / 6 % 6), level(index % 6))
+            }
+            232..=255 => {
+                let grey = 8 + (index - 232) * 10;
+                Rgb::new(grey, grey, grey)
+            }
+        }
+    }
+
+    /// Returns the index of the palette color closest to `color`, out of
+    /// the first 16 or all 256.
+    pub fn nearest(&self, color: Rgb<u8>, mode: ColorMode) -> u8 {
+        let distance = |index: u8| {
+            let other = self.ansi_value(index);
+            let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+            d(color.r, other.r) + d(color.g, other.g) + d(color.b, other.b)
+        };
+        // the closest cube color is the closest level of every channel
+        let level = |value: u8| match value {
+            0..=47 => 0,
+            48..=114 => 1,
+            _ => (value - 35) / 40,
+        };
+        let average = (color.r as u16 + color.g as u16 + color.b as u16) / 3;
+        let grey = (average.saturating_sub(3) / 10).min(23) as u8;
+        let extended = [
+            16 + 36 * level(color.r) + 6 * level(color.g) + level(color.b),
+            232 + grey,
+            232 + (grey + 1).min(23),
+        ];
+        let extended = match mode {
+            ColorMode::Ansi256 => &extended[..],
+            _ => &[],
+        };
+        (0..16)
+            .chain(extended.iter().copied())
+            .min_by_key(|index| distance(*index))
+            .unwrap_or_default()
+    }
+
+    /// Returns the terminal color closest to `color` that can be shown in
+    /// `mode`.
+    pub fn quantize(&self, color: Rgb<u8>, mode: ColorMode) -> Color {
+        match mode {
+            ColorMode::TrueColor => Color::Rgb {
+                r: color.r,
+                g: color.g,
+                b: color.b,
+            },
+            mode => Color::AnsiValue(self.nearest(color, mode)),
+        }
+    }
+
+    /// Returns the RGB value of a color, or `None` for [`Color::Reset`],
+    /// which is whatever the terminal uses by default.
+    pub fn to_rgb(&self, color: Color) -> Option<Rgb<u8>> {
+        let index = match color {
+            Color::Reset => return None,
+            Color::Rgb { r, g, b } => return Some(Rgb::new(r, g, b)),
+            Color::AnsiValue(index) => index,
+            Color::Black => 0,
+            Color::DarkRed => 1,
+            Color::DarkGreen => 2,
+            Color::DarkYellow => 3,
+            Color::DarkBlue => 4,
+            Color::DarkMagenta => 5,
+            Color::DarkCyan => 6,
+            Color::Grey => 7,
+            Color::DarkGrey => 8,
+            Color::Red => 9,
+            Color::Green => 10,
+            Color::Yellow => 11,
+            Color::Blue => 12,
+            Color::Magenta => 13,
+            Color::Cyan => 14,
+            Color::White => 15,
+        };
+        Some(self.ansi_value(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named_colors() {
+        let palette = Palette::default();
+        assert_eq!(palette.to_rgb(Color::Black), Some(Rgb::new(0, 0, 0)));
+        assert_eq!(palette.to_rgb(Color::Red), Some(Rgb::new(255, 0, 0)));
+        assert_eq!(
+            palette.to_rgb(Color::DarkGrey),
+            palette.to_rgb(Color::AnsiValue(8))
+        );
+        assert_eq!(
+            Palette::vga().to_rgb(Color::DarkYellow),
+            Some(Rgb::new(170, 85, 0))
+        );
+        assert_eq!(palette.to_rgb(Color::Reset), None);
+        assert_eq!(
+            palette.to_rgb(Color::Rgb { r: 1, g: 2, b: 3 }),
+            Some(Rgb::new(1, 2, 3))
+        );
+    }
+
+    #[test]
+    fn ansi_values() {
+        let palette = Palette::default();
+        assert_eq!(palette.ansi_value(16), Rgb::new(0, 0, 0));
+        assert_eq!(palette.ansi_value(196), Rgb::new(255, 0, 0));
+        assert_eq!(palette.ansi_value(110), Rgb::new(135, 175, 215));
+        assert_eq!(palette.ansi_value(231), Rgb::new(255, 255, 255));
+        assert_eq!(palette.ansi_value(232), Rgb::new(8, 8, 8));
+        assert_eq!(palette.ansi_value(255), Rgb::new(238, 238, 238));
+    }
+
+    #[test]
+    fn nearest() {
+        let palette = Palette::default();
+        let orange = Rgb::new(250, 130, 10);
+        assert_eq!(palette.nearest(orange, ColorMode::Ansi256), 208);
+        assert_eq!(palette.nearest(orange, ColorMode::Ansi16), 3);
+        assert_eq!(
+            palette.nearest(Rgb::new(120, 121, 119), ColorMode::Ansi256),
+            243
+        );
+        assert_eq!(palette.nearest(Rgb::new(255, 0, 0), ColorMode::Ansi256), 9);
+        assert_eq!(
+            palette.quantize(orange, ColorMode::TrueColor),
+            Color::Rgb {
+                r: 250,
+                g: 130,
+                b: 10
+            }
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use super::Paint;
+
+/// What part of the circle an [`Arc`] covers.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub enum ArcKind {
+    /// A curved line along the circle.
+    #[default]
+    Stroke,
+    /// A wedge from the centre out to the circle.
+    Pie,
+    /// A band between an inner radius and the circle.
+    Donut { inner_radius: f32 },
+}
+
+/// A segment of a circle between two angles.
+///
+/// Angles are in radians, starting from the positive x axis and turning
+/// clockwise on screen, since canvas y grows downwards.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Arc {
+    pub radius: f32,
+    pub start_angle: f32,
+    pub end_angle: f32,
+    pub kind: ArcKind,
+    /// The line width for [`ArcKind::Stroke`].
+    pub stroke_width: Option<f32>,
+    pub stroke_color: Option<Paint>,
+}
+
+impl Arc {
+    pub fn new(radius: f32, start_angle: f32, end_angle: f32) -> Self {
+        Self {
+            radius,
+            start_angle,
+            end_angle,
+            kind: ArcKind::Stroke,
+            stroke_width: None,
+            stroke_color: None,
+        }
+    }
+}
+
+pub trait ArcLike: Sized + AsMut<Arc> + AsRef<Arc> {
+    type Like: AsMut<Arc> + AsRef<Arc>;
+
+    fn like(self) -> Self::Like;
+
+    fn pie(self) -> Self::Like {
+        let mut arc = self.like();
+        arc.as_mut().kind = ArcKind::Pie;
+        arc
+    }
+
+    fn donut(self, inner_radius: f32) -> Self::Like {
+        let mut arc = self.like();
+        arc.as_mut().kind = ArcKind::Donut { inner_radius };
+        arc
+    }
+
+    fn stroke(self, stroke: f32) -> Self::Like {
+        let mut arc = self.like();
+        arc.as_mut().kind = ArcKind::Stroke;
+        arc.as_mut().stroke_width = Some(stroke);
+        arc
+    }
+
+    fn stroke_color(self, color: impl Into<Paint>) -> Self::Like {
+        let mut arc = self.like();
+        arc.as_mut().stroke_color = Some(color.into());
+        arc
+    }
+
+    fn maybe_stroke_color(self, color: Option<impl Into<Paint>>) -> Self::Like {
+        let mut arc = self.like();
+        arc.as_mut().stroke_color = color.map(Into::into);
+        arc
+    }
+}
+
+impl AsRef<Arc> for Arc {
+    fn as_ref(&self) -> &Arc {
+        self
+    }
+}
+
+impl AsMut<Arc> for Arc {
+    fn as_mut(&mut self) -> &mut Arc {
+        self
+    }
+}
+
+impl ArcLike for Arc {
+    type Like = Arc;
+
+    fn like(self) -> Self::Like {
+        self
+    }
+}
@@ -0,0 +1,129 @@
+use std::f32::consts::PI;
+
+/// Renders the canvas with plain ASCII characters instead of half blocks,
+/// for terminals, consoles and logs that cannot show block characters.
+///
+/// Every cell is turned into a character from the ramp by how light its two
+/// pixels are. Printed text is left as it is, spaces and backgrounds
+/// included.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ascii {
+    /// The characters from darkest to lightest.
+    pub ramp: Vec<char>,
+    /// Whether characters keep the color of their pixels. When `false`, they
+    /// are printed in the default color of the terminal.
+    pub colored: bool,
+    /// How much the lightness has to change across a cell, from `0.0` to
+    /// `1.0`, for it to be drawn as an edge with `-`, `|`, `/` or `\`
+    /// instead of a character from the ramp. `None` draws no edges.
+    pub edge_threshold: Option<f32>,
+}
+
+impl Default for Ascii {
+    fn default() -> Self {
+        Self {
+            ramp: Self::RAMP.chars().collect(),
+            colored: true,
+            edge_threshold: None,
+        }
+    }
+}
+
+impl Ascii {
+    /// The default ramp.
+    pub const RAMP: &'static str = " .:-=+*#%@";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the character for a lightness from `0.0` to `1.0`.
+    pub fn shade(&self, lightness: f32) -> char {
+        let last = self.ramp.len().saturating_sub(1);
+        let index = (lightness.clamp(0.0, 1.0) * last as f32).round() as usize;
+        self.ramp.get(index).copied().unwrap_or(' ')
+    }
+
+    /// Returns the character for an edge across which the lightness grows
+    /// in `direction`, in radians with y pointing down.
+    pub fn edge(direction: f32) -> char {
+        // edges run across the direction the lightness changes in
+        let eighths = (direction.rem_euclid(PI) / (PI / 4.0)).round() as u8;
+        match eighths % 4 {
+            0 => '|',
+            1 => '/',
+            2 => '-',
+            _ => '\\',
+        }
+    }
+}
+
+pub trait AsciiLike: Sized + AsMut<Ascii> + AsRef<Ascii> {
+    type Like: AsMut<Ascii> + AsRef<Ascii>;
+
+    fn like(self) -> Self::Like;
+
+    fn ramp(self, ramp: &str) -> Self::Like {
+        let mut ascii = self.like();
+        ascii.as_mut().ramp = ramp.chars().collect();
+        ascii
+    }
+
+    fn colored(self, colored: bool) -> Self::Like {
+        let mut ascii = self.like();
+        ascii.as_mut().colored = colored;
+        ascii
+    }
+
+    fn edges(self, threshold: f32) -> Self::Like {
+        let mut ascii = self.like();
+        ascii.as_mut().edge_threshold = Some(threshold);
+        ascii
+    }
+}
+
+impl AsRef<Ascii> for Ascii {
+    fn as_ref(&self) -> &Ascii {
+        self
+    }
+}
+
+impl AsMut<Ascii> for Ascii {
+    fn as_mut(&mut self) -> &mut Ascii {
+        self
+    }
+}
+
+impl AsciiLike for Ascii {
+    type Like = Ascii;
+
+    fn like(self) -> Self::Like {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_4;
+
+    use super::*;
+
+    #[test]
+    fn shade() {
+        let ascii = Ascii::new();
+        assert_eq!(ascii.shade(0.0), ' ');
+        assert_eq!(ascii.shade(0.5), '+');
+        assert_eq!(ascii.shade(1.0), '@');
+        assert_eq!(ascii.shade(2.0), '@');
+        assert_eq!(Ascii::new().ramp("").shade(0.5), ' ');
+    }
+
+    #[test]
+    fn edge() {
+        assert_eq!(Ascii::edge(0.0), '|');
+        assert_eq!(Ascii::edge(PI), '|');
+        assert_eq!(Ascii::edge(FRAC_PI_4), '/');
+        assert_eq!(Ascii::edge(2.0 * FRAC_PI_4), '-');
+        assert_eq!(Ascii::edge(-FRAC_PI_4), '\\');
+    }
+}
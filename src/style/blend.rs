@@ -0,0 +1,75 @@
+use rgb::Rgb;
+
+/// How a drawn color combines with the color already on the canvas.
+///
+/// Anti-aliased edges fade between the blended color and the canvas color,
+/// so every mode keeps smooth edges.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum BlendMode {
+    /// The drawn color replaces the canvas color.
+    #[default]
+    Normal,
+    /// The colors are added, brightening the canvas, as for glowing
+    /// particles.
+    Add,
+    /// The colors are multiplied, darkening the canvas, as for shadows.
+    Multiply,
+    /// The inverted colors are multiplied, brightening the canvas more
+    /// gently than adding.
+    Screen,
+    /// Multiplies dark canvas colors and screens light ones, adding
+    /// contrast.
+    Overlay,
+    /// The lighter of the two colors, per channel.
+    Lighten,
+    /// The darker of the two colors, per channel.
+    Darken,
+    /// The absolute difference between the colors, per channel.
+    Difference,
+}
+
+impl BlendMode {
+    /// Returns the result of drawing `color` over `backdrop`.
+    pub fn blend(self, color: Rgb<u8>, backdrop: Rgb<u8>) -> Rgb<u8> {
+        let channel = |source: u8, backdrop: u8| {
+            let (s, b) = (source as f32 / 255.0, backdrop as f32 / 255.0);
+            let result = match self {
+                Self::Normal => s,
+                Self::Add => s + b,
+                Self::Multiply => s * b,
+                Self::Screen => 1.0 - (1.0 - s) * (1.0 - b),
+                Self::Overlay if b < 0.5 => 2.0 * s * b,
+                Self::Overlay => 1.0 - 2.0 * (1.0 - s) * (1.0 - b),
+                Self::Lighten => s.max(b),
+                Self::Darken => s.min(b),
+                Self::Difference => (s - b).abs(),
+            };
+            (result.clamp(0.0, 1.0) * 255.0).round() as u8
+        };
+        Rgb::new(
+            channel(color.r, backdrop.r),
+            channel(color.g, backdrop.g),
+            channel(color.b, backdrop.b),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modes() {
+        let color = Rgb::new(200, 100, 0);
+        let backdrop = Rgb::new(100, 200, 255);
+        let blend = |mode: BlendMode| mode.blend(color, backdrop);
+        assert_eq!(blend(BlendMode::Normal), color);
+        assert_eq!(blend(BlendMode::Add), Rgb::new(255, 255, 255));
+        assert_eq!(blend(BlendMode::Multiply), Rgb::new(78, 78, 0));
+        assert_eq!(blend(BlendMode::Screen), Rgb::new(222, 222, 255));
+        assert_eq!(blend(BlendMode::Overlay), Rgb::new(157, 188, 255));
+        assert_eq!(blend(BlendMode::Lighten), Rgb::new(200, 200, 255));
+        assert_eq!(blend(BlendMode::Darken), Rgb::new(100, 100, 0));
+        assert_eq!(blend(BlendMode::Difference), Rgb::new(100, 100, 255));
+    }
+}
@@ -0,0 +1,85 @@
+use glam::Vec2;
+
+use super::Paint;
+
+/// An ellipse with separate horizontal and vertical radii.
+///
+/// The stroke is centred on the outline. If no stroke width is set, a one
+/// pixel outline is drawn.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Ellipse {
+    pub radii: Vec2,
+    /// The rotation around the centre, in radians.
+    pub rotation: f32,
+    pub stroke_width: Option<f32>,
+    pub stroke_color: Option<Paint>,
+    pub solid: bool,
+}
+
+impl Ellipse {
+    pub fn with_radii(radii: Vec2) -> Self {
+        Self {
+            radii,
+            rotation: 0.0,
+            stroke_width: None,
+            stroke_color: None,
+            solid: false,
+        }
+    }
+}
+
+pub trait EllipseLike: Sized + AsMut<Ellipse> + AsRef<Ellipse> {
+    type Like: AsMut<Ellipse> + AsRef<Ellipse>;
+
+    fn like(self) -> Self::Like;
+
+    fn rotation(self, radians: f32) -> Self::Like {
+        let mut ellipse = self.like();
+        ellipse.as_mut().rotation = radians;
+        ellipse
+    }
+
+    fn stroke(self, stroke: f32) -> Self::Like {
+        let mut ellipse = self.like();
+        ellipse.as_mut().stroke_width = Some(stroke);
+        ellipse
+    }
+
+    fn stroke_color(self, color: impl Into<Paint>) -> Self::Like {
+        let mut ellipse = self.like();
+        ellipse.as_mut().stroke_color = Some(color.into());
+        ellipse
+    }
+
+    fn maybe_stroke_color(self, color: Option<impl Into<Paint>>) -> Self::Like {
+        let mut ellipse = self.like();
+        ellipse.as_mut().stroke_color = color.map(Into::into);
+        ellipse
+    }
+
+    fn solid(self) -> Self::Like {
+        let mut ellipse = self.like();
+        ellipse.as_mut().solid = true;
+        ellipse
+    }
+}
+
+impl AsRef<Ellipse> for Ellipse {
+    fn as_ref(&self) -> &Ellipse {
+        self
+    }
+}
+
+impl AsMut<Ellipse> for Ellipse {
+    fn as_mut(&mut self) -> &mut Ellipse {
+        self
+    }
+}
+
+impl EllipseLike for Ellipse {
+    type Like = Ellipse;
+
+    fn like(self) -> Self::Like {
+        self
+    }
+}
@@ -0,0 +1,108 @@
+use crossterm::style::Color;
+
+/// Which neighbours of a pixel a flood fill spreads to.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Only the pixels above, below, left and right.
+    #[default]
+    Four,
+    /// The diagonal pixels as well, so the fill leaks through diagonal gaps.
+    Eight,
+}
+
+/// A bucket fill that replaces a connected region of similar pixels.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FloodFill {
+    /// The color the region is filled with.
+    pub color: Color,
+    /// How far each RGB channel may be from the starting pixel for a pixel
+    /// to be part of the region. Other colors must match exactly.
+    pub tolerance: u8,
+    /// Which neighbours the fill spreads to.
+    pub connectivity: Connectivity,
+}
+
+impl FloodFill {
+    pub fn with_color(color: Color) -> Self {
+        Self {
+            color,
+            tolerance: 0,
+            connectivity: Connectivity::default(),
+        }
+    }
+
+    /// Whether a pixel belongs to the region started at a pixel of `target`.
+    pub(crate) fn matches(&self, target: Option<Color>, color: Option<Color>) -> bool {
+        match (target, color) {
+            (
+                Some(Color::Rgb { r, g, b }),
+                Some(Color::Rgb {
+                    r: other_r,
+                    g: other_g,
+                    b: other_b,
+                }),
+            ) => {
+                r.abs_diff(other_r) <= self.tolerance
+                    && g.abs_diff(other_g) <= self.tolerance
+                    && b.abs_diff(other_b) <= self.tolerance
+            }
+            (target, color) => target == color,
+        }
+    }
+}
+
+pub trait FloodFillLike: Sized + AsMut<FloodFill> + AsRef<FloodFill> {
+    type Like: AsMut<FloodFill> + AsRef<FloodFill>;
+
+    fn like(self) -> Self::Like;
+
+    fn tolerance(self, tolerance: u8) -> Self::Like {
+        let mut fill = self.like();
+        fill.as_mut().tolerance = tolerance;
+        fill
+    }
+
+    fn connectivity(self, connectivity: Connectivity) -> Self::Like {
+        let mut fill = self.like();
+        fill.as_mut().connectivity = connectivity;
+        fill
+    }
+}
+
+impl AsRef<FloodFill> for FloodFill {
+    fn as_ref(&self) -> &FloodFill {
+        self
+    }
+}
+
+impl AsMut<FloodFill> for FloodFill {
+    fn as_mut(&mut self) -> &mut FloodFill {
+        self
+    }
+}
+
+impl FloodFillLike for FloodFill {
+    type Like = FloodFill;
+
+    fn like(self) -> Self::Like {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tolerance() {
+        let fill = FloodFill::with_color(Color::Red).tolerance(10);
+        let grey = |v| Some(Color::Rgb { r: v, g: v, b: v });
+        assert!(fill.matches(grey(100), grey(110)));
+        assert!(fill.matches(grey(100), grey(90)));
+        assert!(!fill.matches(grey(100), grey(111)));
+        assert!(!fill.matches(grey(100), None));
+        assert!(fill.matches(None, None));
+        assert!(fill.matches(Some(Color::Blue), Some(Color::Blue)));
+        assert!(!fill.matches(Some(Color::Blue), Some(Color::DarkBlue)));
+    }
+}
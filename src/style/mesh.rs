@@ -0,0 +1,137 @@
+use glam::Vec3;
+use rgb::Rgb;
+
+/// How the faces of a mesh are drawn.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Shading {
+    /// Only the edges of faces are drawn, as anti-aliased lines.
+    Wireframe,
+    /// Faces are filled with one color each, lit by a directional light.
+    #[default]
+    Flat,
+}
+
+/// The style of a [`Mesh`](crate::mesh::Mesh) drawn with
+/// [`State::mesh`](crate::State::mesh).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MeshStyle {
+    pub shading: Shading,
+    pub color: Rgb<u8>,
+    /// The direction the light travels in world coordinates.
+    pub light: Vec3,
+    /// How bright faces turned away from the light are, from `0.0` to
+    /// `1.0`.
+    pub ambient: f32,
+    /// Whether faces turned away from the camera are skipped.
+    pub cull_backfaces: bool,
+}
+
+impl Default for MeshStyle {
+    fn default() -> Self {
+        Self {
+            shading: Shading::default(),
+            color: Rgb::new(255, 255, 255),
+            light: Vec3::new(-1.0, -2.0, -1.5),
+            ambient: 0.2,
+            cull_backfaces: true,
+        }
+    }
+}
+
+impl MeshStyle {
+    pub fn wireframe() -> Self {
+        Self {
+            shading: Shading::Wireframe,
+            ..Default::default()
+        }
+    }
+
+    pub fn flat() -> Self {
+        Self::default()
+    }
+
+    /// Returns the color of a face with the given normal.
+    pub fn shade(&self, normal: Vec3) -> Rgb<u8> {
+        let lit = normal
+            .normalize_or_zero()
+            .dot(-self.light.normalize_or_zero())
+            .max(0.0);
+        let ambient = self.ambient.clamp(0.0, 1.0);
+        let brightness = ambient + (1.0 - ambient) * lit;
+        let scale = |channel: u8| (channel as f32 * brightness).round() as u8;
+        Rgb::new(
+            scale(self.color.r),
+            scale(self.color.g),
+            scale(self.color.b),
+        )
+    }
+}
+
+pub trait MeshStyleLike: Sized + AsMut<MeshStyle> + AsRef<MeshStyle> {
+    type Like: AsMut<MeshStyle> + AsRef<MeshStyle>;
+
+    fn like(self) -> Self::Like;
+
+    fn shading(self, shading: Shading) -> Self::Like {
+        let mut style = self.like();
+        style.as_mut().shading = shading;
+        style
+    }
+
+    fn color(self, color: Rgb<u8>) -> Self::Like {
+        let mut style = self.like();
+        style.as_mut().color = color;
+        style
+    }
+
+    fn light(self, direction: Vec3) -> Self::Like {
+        let mut style = self.like();
+        style.as_mut().light = direction;
+        style
+    }
+
+    fn ambient(self, ambient: f32) -> Self::Like {
+        let mut style = self.like();
+        style.as_mut().ambient = ambient;
+        style
+    }
+
+    fn cull_backfaces(self, cull: bool) -> Self::Like {
+        let mut style = self.like();
+        style.as_mut().cull_backfaces = cull;
+        style
+    }
+}
+
+impl AsRef<MeshStyle> for MeshStyle {
+    fn as_ref(&self) -> &MeshStyle {
+        self
+    }
+}
+
+impl AsMut<MeshStyle> for MeshStyle {
+    fn as_mut(&mut self) -> &mut MeshStyle {
+        self
+    }
+}
+
+impl MeshStyleLike for MeshStyle {
+    type Like = MeshStyle;
+
+    fn like(self) -> Self::Like {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shade() {
+        let style = MeshStyle::flat().light(Vec3::NEG_Z).ambient(0.2);
+        assert_eq!(style.shade(Vec3::Z), Rgb::new(255, 255, 255));
+        assert_eq!(style.shade(Vec3::NEG_Z), Rgb::new(51, 51, 51));
+        assert_eq!(style.shade(Vec3::X), Rgb::new(51, 51, 51));
+    }
+}
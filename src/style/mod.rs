@@ -3,6 +3,34 @@ use crossterm::style::{Attribute, Attributes, Color, ContentStyle};
 use glam::{U16Vec2, Vec2};
 use paste::paste;
 
+mod arc;
+mod ascii;
+mod blend;
+mod ellipse;
+mod flood_fill;
+mod mesh;
+mod paint;
+mod path;
+mod polygon;
+mod rect;
+mod stroke;
+mod stroke_text;
+mod triangle;
+
+pub use arc::{Arc, ArcKind, ArcLike};
+pub use ascii::{Ascii, AsciiLike};
+pub use blend::BlendMode;
+pub use ellipse::{Ellipse, EllipseLike};
+pub use flood_fill::{Connectivity, FloodFill, FloodFillLike};
+pub use mesh::{MeshStyle, MeshStyleLike, Shading};
+pub use paint::{ColorStop, Gradient, GradientKind, GradientLike, Paint, Spread};
+pub use path::{Path, PathCommand, PathError, PathLike};
+pub use polygon::{FillRule, Polygon, PolygonLike};
+pub use rect::{Rect, RectLike};
+pub use stroke::{Dash, DashLike, LineCap, LineJoin, Stroke, StrokeLike};
+pub use stroke_text::{StrokeText, StrokeTextLike};
+pub use triangle::{Triangle, TriangleLike, Vertex};
+
 macro_rules! attribute_function {
     (Attribute::$attribute:ident) => {
         paste! {
@@ -480,19 +508,25 @@ impl Stylize for PrintStyle {
     }
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct StyledPrint<'a> {
     /// A content to apply the style on.
     content: &'a str,
     /// The style (colors, content attributes).
     style: PrintStyle,
+    /// Paints the background instead of the background color.
+    background_paint: Option<Paint>,
 }
 
 impl<'a> StyledPrint<'a> {
     /// Creates a new `StyledContent`.
     #[inline]
     pub fn new(content: &'a str, style: PrintStyle) -> StyledPrint<'a> {
-        StyledPrint { style, content }
+        StyledPrint {
+            style,
+            content,
+            background_paint: None,
+        }
     }
 
     /// Returns the content.
@@ -513,6 +547,22 @@ impl<'a> StyledPrint<'a> {
     pub fn style_mut(&mut self) -> &mut PrintStyle {
         &mut self.style
     }
+
+    /// Returns the background paint.
+    #[inline]
+    pub fn background_paint(&self) -> Option<&Paint> {
+        self.background_paint.as_ref()
+    }
+
+    /// Fills the background, including padding and borders, with a paint
+    /// such as a gradient.
+    ///
+    /// The padding and borders are painted per pixel. Cells with text only
+    /// have one background color, taken from the middle of the cell.
+    pub fn on_paint(mut self, paint: impl Into<Paint>) -> Self {
+        self.background_paint = Some(paint.into());
+        self
+    }
 }
 
 impl AsRef<PrintStyle> for StyledPrint<'_> {
@@ -545,16 +595,17 @@ impl<'a> From<&'a str> for StyledPrint<'a> {
         StyledPrint {
             content: val,
             style: PrintStyle::default(),
+            background_paint: None,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Circle {
     pub radius: f32,
     pub outer_stroke: Option<f32>,
     pub inner_stroke: Option<f32>,
-    pub stroke_color: Option<Rgb<u8>>,
+    pub stroke_color: Option<Paint>,
 }
 
 impl Circle {
@@ -596,15 +647,15 @@ pub trait CircleLike: Sized + AsMut<Circle> + AsRef<Circle> {
         circle
     }
 
-    fn stroke_color(self, color: Rgb<u8>) -> Self::Like {
+    fn stroke_color(self, color: impl Into<Paint>) -> Self::Like {
         let mut circle = self.like();
-        circle.as_mut().stroke_color = Some(color);
+        circle.as_mut().stroke_color = Some(color.into());
         circle
     }
 
-    fn maybe_stroke_color(self, color: Option<Rgb<u8>>) -> Self::Like {
+    fn maybe_stroke_color(self, color: Option<impl Into<Paint>>) -> Self::Like {
         let mut circle = self.like();
-        circle.as_mut().stroke_color = color;
+        circle.as_mut().stroke_color = color.map(Into::into);
         circle
     }
 
@@ -643,14 +694,27 @@ macro_rules! calculated_docs {
 #[allow(clippy::single_component_path_imports)]
 #[allow(clippy::useless_attribute)]
 use calculated_docs;
-use rgb::Rgb;
 
 #[cfg(test)]
 mod tests {
     use crossterm::style::{Attribute, Color};
+    use rgb::Rgb;
 
     use super::*;
 
+    #[test]
+    fn maybe_stroke_color() {
+        let red = Rgb::new(255, 0, 0);
+        let circle = Circle::with_radius(1.0).maybe_stroke_color(Some(red));
+        assert_eq!(circle.stroke_color, Some(Paint::Solid(red)));
+        let rect = Rect::square(1.0).maybe_stroke_color(None::<Paint>);
+        assert_eq!(rect.stroke_color, None);
+        let ellipse = Ellipse::with_radii(Vec2::ONE).maybe_stroke_color(Some(red));
+        assert_eq!(ellipse.stroke_color, Some(Paint::Solid(red)));
+        let arc = Arc::new(1.0, 0.0, 1.0).maybe_stroke_color(None::<Paint>);
+        assert_eq!(arc.stroke_color, None);
+    }
+
     #[test]
     fn set_fg_bg_add_attr() {
         let style = PrintStyle::default()
@@ -0,0 +1,341 @@
+use std::f32::consts::TAU;
+
+use glam::Vec2;
+use rgb::Rgb;
+
+use crate::color::{interpolate, ColorSpace};
+
+/// How a gradient continues past its first and last stops.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Spread {
+    /// The end colors carry on forever.
+    #[default]
+    Pad,
+    /// The gradient starts over from the first stop.
+    Repeat,
+    /// The gradient runs backwards and forwards.
+    Reflect,
+}
+
+/// The shape of a gradient, in canvas coordinates.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum GradientKind {
+    /// Colors change along the line from `start` to `end`.
+    Linear { start: Vec2, end: Vec2 },
+    /// Colors change outwards from `center`, reaching the last stop at
+    /// `radius`.
+    Radial { center: Vec2, radius: f32 },
+    /// Colors sweep clockwise around `center`, starting at `angle` radians.
+    Conic { center: Vec2, angle: f32 },
+}
+
+/// A color at a position along a gradient, from `0.0` to `1.0`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ColorStop {
+    pub offset: f32,
+    pub color: Rgb<u8>,
+}
+
+/// A smooth blend between colors, evaluated for every pixel it paints.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    pub kind: GradientKind,
+    /// The stops, sorted by offset.
+    pub stops: Vec<ColorStop>,
+    pub spread: Spread,
+    /// The color space colors are mixed in between stops.
+    pub color_space: ColorSpace,
+}
+
+impl Gradient {
+    pub fn new(kind: GradientKind) -> Self {
+        Self {
+            kind,
+            stops: vec![],
+            spread: Spread::default(),
+            color_space: ColorSpace::default(),
+        }
+    }
+
+    pub fn linear(start: Vec2, end: Vec2) -> Self {
+        Self::new(GradientKind::Linear { start, end })
+    }
+
+    pub fn radial(center: Vec2, radius: f32) -> Self {
+        Self::new(GradientKind::Radial { center, radius })
+    }
+
+    pub fn conic(center: Vec2, angle: f32) -> Self {
+        Self::new(GradientKind::Conic { center, angle })
+    }
+
+    /// Returns the color of the gradient at a canvas position.
+    ///
+    /// A gradient without stops is white.
+    pub fn color_at(&self, pos: Vec2) -> Rgb<u8> {
+        let (first, last) = match (self.stops.first(), self.stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Rgb::new(255, 255, 255),
+        };
+
+        let t = self.apply_spread(self.offset_at(pos));
+        if !t.is_finite() || t <= first.offset {
+            return first.color;
+        }
+        if t >= last.offset {
+            return last.color;
+        }
+
+        let next = self.stops.partition_point(|stop| stop.offset <= t);
+        let (from, to) = (&self.stops[next - 1], &self.stops[next]);
+        let amount = (t - from.offset) / (to.offset - from.offset);
+        interpolate(from.color, to.color, amount, self.color_space)
+    }
+
+    /// The position along the gradient before the spread is applied.
+    fn offset_at(&self, pos: Vec2) -> f32 {
+        match self.kind {
+            GradientKind::Linear { start, end } => {
+                let direction = end - start;
+                let length_sq = direction.length_squared();
+                if length_sq <= f32::EPSILON {
+                    return 0.0;
+                }
+                (pos - start).dot(direction) / length_sq
+            }
+            GradientKind::Radial { center, radius } => {
+                if radius <= f32::EPSILON {
+                    return 1.0;
+                }
+                pos.distance(center) / radius
+            }
+            GradientKind::Conic { center, angle } => {
+                let turn = (pos - center).to_angle() - angle;
+                turn.rem_euclid(TAU) / TAU
+            }
+        }
+    }
+
+    fn apply_spread(&self, t: f32) -> f32 {
+        match self.spread {
+            Spread::Pad => t,
+            Spread::Repeat => t.rem_euclid(1.0),
+            Spread::Reflect => {
+                let t = t.rem_euclid(2.0);
+                if t > 1.0 {
+                    2.0 - t
+                } else {
+                    t
+                }
+            }
+        }
+    }
+}
+
+pub trait GradientLike: Sized + AsMut<Gradient> + AsRef<Gradient> {
+    type Like: AsMut<Gradient> + AsRef<Gradient>;
+
+    fn like(self) -> Self::Like;
+
+    /// Adds a color stop, keeping the stops in order. Stops with a NaN
+    /// offset are ignored.
+    fn stop(self, offset: f32, color: Rgb<u8>) -> Self::Like {
+        let mut gradient = self.like();
+        if offset.is_nan() {
+            return gradient;
+        }
+        let stops = &mut gradient.as_mut().stops;
+        let index = stops.partition_point(|stop| stop.offset <= offset);
+        stops.insert(index, ColorStop { offset, color });
+        gradient
+    }
+
+    fn spread(self, spread: Spread) -> Self::Like {
+        let mut gradient = self.like();
+        gradient.as_mut().spread = spread;
+        gradient
+    }
+
+    /// Mixes the colors between stops in another color space, such as
+    /// [`ColorSpace::Oklab`] for even gradients without muddy middles.
+    fn color_space(self, color_space: ColorSpace) -> Self::Like {
+        let mut gradient = self.like();
+        gradient.as_mut().color_space = color_space;
+        gradient
+    }
+}
+
+impl AsRef<Gradient> for Gradient {
+    fn as_ref(&self) -> &Gradient {
+        self
+    }
+}
+
+impl AsMut<Gradient> for Gradient {
+    fn as_mut(&mut self) -> &mut Gradient {
+        self
+    }
+}
+
+impl GradientLike for Gradient {
+    type Like = Gradient;
+
+    fn like(self) -> Self::Like {
+        self
+    }
+}
+
+/// What a shape is filled with.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Paint {
+    Solid(Rgb<u8>),
+    Gradient(Gradient),
+}
+
+impl Paint {
+    /// Returns the color at a canvas position.
+    pub fn color_at(&self, pos: Vec2) -> Rgb<u8> {
+        match self {
+            Self::Solid(color) => *color,
+            Self::Gradient(gradient) => gradient.color_at(pos),
+        }
+    }
+}
+
+impl Default for Paint {
+    fn default() -> Self {
+        Self::Solid(Rgb::new(255, 255, 255))
+    }
+}
+
+impl From<Rgb<u8>> for Paint {
+    fn from(color: Rgb<u8>) -> Self {
+        Self::Solid(color)
+    }
+}
+
+impl From<Gradient> for Paint {
+    fn from(gradient: Gradient) -> Self {
+        Self::Gradient(gradient)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: Rgb<u8> = Rgb::new(0, 0, 0);
+    const WHITE: Rgb<u8> = Rgb::new(255, 255, 255);
+
+    fn black_to_white(kind: GradientKind) -> Gradient {
+        Gradient::new(kind).stop(0.0, BLACK).stop(1.0, WHITE)
+    }
+
+    #[test]
+    fn linear() {
+        let gradient = black_to_white(GradientKind::Linear {
+            start: Vec2::ZERO,
+            end: Vec2::new(10.0, 0.0),
+        });
+        assert_eq!(gradient.color_at(Vec2::ZERO), BLACK);
+        assert_eq!(
+            gradient.color_at(Vec2::new(5.0, 7.0)),
+            Rgb::new(128, 128, 128)
+        );
+        assert_eq!(gradient.color_at(Vec2::new(10.0, -3.0)), WHITE);
+        assert_eq!(gradient.color_at(Vec2::new(-5.0, 0.0)), BLACK);
+        assert_eq!(gradient.color_at(Vec2::new(15.0, 0.0)), WHITE);
+    }
+
+    #[test]
+    fn spread() {
+        let gradient = black_to_white(GradientKind::Linear {
+            start: Vec2::ZERO,
+            end: Vec2::new(10.0, 0.0),
+        });
+        let at = |gradient: &Gradient, x| gradient.color_at(Vec2::new(x, 0.0));
+
+        let repeat = gradient.clone().spread(Spread::Repeat);
+        assert_eq!(at(&repeat, 12.5), Rgb::new(64, 64, 64));
+        assert_eq!(at(&repeat, -2.5), Rgb::new(191, 191, 191));
+
+        let reflect = gradient.spread(Spread::Reflect);
+        assert_eq!(at(&reflect, 12.5), Rgb::new(191, 191, 191));
+        assert_eq!(at(&reflect, -2.5), Rgb::new(64, 64, 64));
+    }
+
+    #[test]
+    fn radial_and_conic() {
+        let radial = black_to_white(GradientKind::Radial {
+            center: Vec2::ONE,
+            radius: 4.0,
+        });
+        assert_eq!(radial.color_at(Vec2::ONE), BLACK);
+        assert_eq!(radial.color_at(Vec2::new(1.0, 5.0)), WHITE);
+        assert_eq!(
+            radial.color_at(Vec2::new(-1.0, 1.0)),
+            Rgb::new(128, 128, 128)
+        );
+
+        let conic = black_to_white(GradientKind::Conic {
+            center: Vec2::ZERO,
+            angle: 0.0,
+        });
+        assert_eq!(conic.color_at(Vec2::new(1.0, 0.0)), BLACK);
+        assert_eq!(
+            conic.color_at(Vec2::new(-1.0, 0.0)),
+            Rgb::new(128, 128, 128)
+        );
+        assert_eq!(conic.color_at(Vec2::new(0.0, 1.0)), Rgb::new(64, 64, 64));
+    }
+
+    #[test]
+    fn stops() {
+        let red = Rgb::new(255, 0, 0);
+        let gradient = Gradient::linear(Vec2::ZERO, Vec2::new(10.0, 0.0))
+            .stop(1.0, WHITE)
+            .stop(0.0, BLACK)
+            .stop(0.5, red);
+        assert_eq!(gradient.stops[1].color, red);
+        assert_eq!(gradient.color_at(Vec2::new(5.0, 0.0)), red);
+        assert_eq!(
+            gradient.color_at(Vec2::new(7.5, 0.0)),
+            Rgb::new(255, 128, 128)
+        );
+
+        let oklab = Gradient::linear(Vec2::ZERO, Vec2::new(10.0, 0.0))
+            .stop(0.0, BLACK)
+            .stop(1.0, WHITE)
+            .color_space(ColorSpace::Oklab);
+        assert_eq!(oklab.color_at(Vec2::new(5.0, 0.0)), Rgb::new(99, 99, 99));
+
+        let empty = Gradient::linear(Vec2::ZERO, Vec2::ONE);
+        assert_eq!(empty.color_at(Vec2::ZERO), WHITE);
+        assert_eq!(Paint::from(red).color_at(Vec2::ONE), red);
+    }
+
+    #[test]
+    fn non_finite() {
+        let linear = black_to_white(GradientKind::Linear {
+            start: Vec2::ZERO,
+            end: Vec2::new(10.0, 0.0),
+        });
+        assert_eq!(linear.color_at(Vec2::new(f32::NAN, 0.0)), BLACK);
+
+        let repeat = linear.clone().spread(Spread::Repeat);
+        assert_eq!(repeat.color_at(Vec2::new(f32::INFINITY, 0.0)), BLACK);
+
+        let nan_stop = linear.stop(f32::NAN, Rgb::new(255, 0, 0));
+        assert_eq!(nan_stop.stops.len(), 2);
+        assert_eq!(
+            nan_stop.color_at(Vec2::new(5.0, 0.0)),
+            Rgb::new(128, 128, 128)
+        );
+
+        let radial = black_to_white(GradientKind::Radial {
+            center: Vec2::ZERO,
+            radius: f32::NAN,
+        });
+        assert_eq!(radial.color_at(Vec2::ONE), BLACK);
+    }
+}
@@ -0,0 +1,507 @@
+use std::{fmt, str::FromStr};
+
+use glam::Vec2;
+
+use super::{FillRule, Paint, Stroke};
+
+/// A single drawing command of a [`Path`], in absolute canvas coordinates.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PathCommand {
+    /// Starts a new subpath at the point.
+    MoveTo(Vec2),
+    /// A straight line to the point.
+    LineTo(Vec2),
+    /// A quadratic Bézier curve through a control point to the end point.
+    QuadTo(Vec2, Vec2),
+    /// A cubic Bézier curve through two control points to the end point.
+    CubicTo(Vec2, Vec2, Vec2),
+    /// An elliptical arc to the end point, described the same way as in SVG.
+    ArcTo {
+        /// The radii of the ellipse, grown if they are too small to reach
+        /// the end point.
+        radii: Vec2,
+        /// The rotation of the ellipse in radians.
+        rotation: f32,
+        /// Whether the arc takes the long way around the ellipse.
+        large_arc: bool,
+        /// Whether the arc runs clockwise on the canvas.
+        sweep: bool,
+        end: Vec2,
+    },
+    /// A straight line back to the start of the subpath, which closes it.
+    Close,
+}
+
+/// An outline made of lines, curves and arcs, which may have several
+/// subpaths.
+///
+/// Paths can be built one command at a time or parsed from the `d`
+/// attribute of an SVG `<path>`. As with [`Polygon`](super::Polygon), the
+/// path is filled white if neither a fill nor a stroke is set.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Path {
+    /// The drawing commands.
+    pub commands: Vec<PathCommand>,
+    /// The rule used to fill overlapping subpaths.
+    pub fill_rule: FillRule,
+    /// The fill color.
+    pub fill_color: Option<Paint>,
+    /// The outline.
+    pub stroke: Option<Stroke>,
+}
+
+impl Path {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses the `d` attribute of an SVG path.
+    pub fn parse(data: &str) -> Result<Self, PathError> {
+        Ok(Self {
+            commands: Parser::new(data).parse()?,
+            ..Default::default()
+        })
+    }
+
+    pub fn move_to(mut self, point: Vec2) -> Self {
+        self.commands.push(PathCommand::MoveTo(point));
+        self
+    }
+
+    pub fn line_to(mut self, point: Vec2) -> Self {
+        self.commands.push(PathCommand::LineTo(point));
+        self
+    }
+
+    pub fn quad_to(mut self, control: Vec2, end: Vec2) -> Self {
+        self.commands.push(PathCommand::QuadTo(control, end));
+        self
+    }
+
+    pub fn cubic_to(mut self, first: Vec2, second: Vec2, end: Vec2) -> Self {
+        self.commands.push(PathCommand::CubicTo(first, second, end));
+        self
+    }
+
+    pub fn arc_to(
+        mut self,
+        radii: Vec2,
+        rotation: f32,
+        large_arc: bool,
+        sweep: bool,
+        end: Vec2,
+    ) -> Self {
+        self.commands.push(PathCommand::ArcTo {
+            radii,
+            rotation,
+            large_arc,
+            sweep,
+            end,
+        });
+        self
+    }
+
+    pub fn close(mut self) -> Self {
+        self.commands.push(PathCommand::Close);
+        self
+    }
+
+    /// Returns the fill, or `None` if the path is only stroked.
+    pub(crate) fn fill(&self) -> Option<Paint> {
+        match (&self.fill_color, &self.stroke) {
+            (None, Some(_)) => None,
+            (color, _) => Some(color.clone().unwrap_or_default()),
+        }
+    }
+}
+
+impl FromStr for Path {
+    type Err = PathError;
+
+    fn from_str(data: &str) -> Result<Self, Self::Err> {
+        Self::parse(data)
+    }
+}
+
+pub trait PathLike: Sized + AsMut<Path> + AsRef<Path> {
+    type Like: AsMut<Path> + AsRef<Path>;
+
+    fn like(self) -> Self::Like;
+
+    fn fill_rule(self, rule: FillRule) -> Self::Like {
+        let mut path = self.like();
+        path.as_mut().fill_rule = rule;
+        path
+    }
+
+    fn fill_color(self, color: impl Into<Paint>) -> Self::Like {
+        let mut path = self.like();
+        path.as_mut().fill_color = Some(color.into());
+        path
+    }
+
+    fn stroke(self, stroke: Stroke) -> Self::Like {
+        let mut path = self.like();
+        path.as_mut().stroke = Some(stroke);
+        path
+    }
+}
+
+impl AsRef<Path> for Path {
+    fn as_ref(&self) -> &Path {
+        self
+    }
+}
+
+impl AsMut<Path> for Path {
+    fn as_mut(&mut self) -> &mut Path {
+        self
+    }
+}
+
+impl PathLike for Path {
+    type Like = Path;
+
+    fn like(self) -> Self::Like {
+        self
+    }
+}
+
+/// An error from parsing SVG path data. Positions are byte offsets into the
+/// data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathError {
+    /// The path does not start with a move command.
+    MissingMoveTo,
+    /// A character that is not a command was found at the given position.
+    UnexpectedCharacter(usize),
+    /// A number or flag was missing at the given position.
+    ExpectedNumber(usize),
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingMoveTo => write!(f, "path data must start with a move command"),
+            Self::UnexpectedCharacter(pos) => write!(f, "unexpected character at {pos}"),
+            Self::ExpectedNumber(pos) => write!(f, "expected a number at {pos}"),
+        }
+    }
+}
+
+impl std::error::Error for PathError {}
+
+struct Parser<'a> {
+    data: &'a [u8],
+    pos: usize,
+    commands: Vec<PathCommand>,
+    current: Vec2,
+    subpath_start: Vec2,
+    /// The control point to reflect for a smooth curve, if the previous
+    /// command was a curve of the same kind.
+    last_cubic: Option<Vec2>,
+    last_quad: Option<Vec2>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(data: &'a str) -> Self {
+        Self {
+            data: data.as_bytes(),
+            pos: 0,
+            commands: vec![],
+            current: Vec2::ZERO,
+            subpath_start: Vec2::ZERO,
+            last_cubic: None,
+            last_quad: None,
+        }
+    }
+
+    fn parse(mut self) -> Result<Vec<PathCommand>, PathError> {
+        self.skip_separators();
+        let mut command = None;
+        while self.pos < self.data.len() {
+            let byte = self.data[self.pos];
+            if byte.is_ascii_alphabetic() {
+                self.pos += 1;
+                command = Some(byte);
+            } else if matches!(command, Some(b'Z' | b'z')) {
+                return Err(PathError::UnexpectedCharacter(self.pos));
+            }
+
+            let Some(name) = command else {
+                return Err(PathError::MissingMoveTo);
+            };
+            if self.commands.is_empty() && !matches!(name, b'M' | b'm') {
+                return Err(PathError::MissingMoveTo);
+            }
+            self.command(name)?;
+            self.skip_separators();
+
+            // coordinates after a move are implicit lines
+            command = match name {
+                b'M' => Some(b'L'),
+                b'm' => Some(b'l'),
+                _ => Some(name),
+            };
+        }
+        Ok(self.commands)
+    }
+
+    fn command(&mut self, name: u8) -> Result<(), PathError> {
+        let relative = name.is_ascii_lowercase();
+        let origin = if relative { self.current } else { Vec2::ZERO };
+        let (mut last_cubic, mut last_quad) = (None, None);
+
+        match name.to_ascii_uppercase() {
+            b'M' => {
+                let point = origin + self.point()?;
+                self.subpath_start = point;
+                self.push(PathCommand::MoveTo(point), point);
+            }
+            b'L' => {
+                let point = origin + self.point()?;
+                self.push(PathCommand::LineTo(point), point);
+            }
+            b'H' => {
+                let x = self.number()? + origin.x;
+                let point = Vec2::new(x, self.current.y);
+                self.push(PathCommand::LineTo(point), point);
+            }
+            b'V' => {
+                let y = self.number()? + origin.y;
+                let point = Vec2::new(self.current.x, y);
+                self.push(PathCommand::LineTo(point), point);
+            }
+            b'C' => {
+                let first = origin + self.point()?;
+                let second = origin + self.point()?;
+                let end = origin + self.point()?;
+                self.push(PathCommand::CubicTo(first, second, end), end);
+                last_cubic = Some(second);
+            }
+            b'S' => {
+                let first = self.reflect(self.last_cubic);
+                let second = origin + self.point()?;
+                let end = origin + self.point()?;
+                self.push(PathCommand::CubicTo(first, second, end), end);
+                last_cubic = Some(second);
+            }
+            b'Q' => {
+                let control = origin + self.point()?;
+                let end = origin + self.point()?;
+                self.push(PathCommand::QuadTo(control, end), end);
+                last_quad = Some(control);
+            }
+            b'T' => {
+                let control = self.reflect(self.last_quad);
+                let end = origin + self.point()?;
+                self.push(PathCommand::QuadTo(control, end), end);
+                last_quad = Some(control);
+            }
+            b'A' => {
+                let radii = self.point()?;
+                let rotation = self.number()?.to_radians();
+                let large_arc = self.flag()?;
+                let sweep = self.flag()?;
+                let end = origin + self.point()?;
+                let arc = PathCommand::ArcTo {
+                    radii,
+                    rotation,
+                    large_arc,
+                    sweep,
+                    end,
+                };
+                self.push(arc, end);
+            }
+            b'Z' => {
+                self.commands.push(PathCommand::Close);
+                self.current = self.subpath_start;
+            }
+            _ => return Err(PathError::UnexpectedCharacter(self.pos - 1)),
+        }
+
+        self.last_cubic = last_cubic;
+        self.last_quad = last_quad;
+        Ok(())
+    }
+
+    fn push(&mut self, command: PathCommand, end: Vec2) {
+        self.commands.push(command);
+        self.current = end;
+    }
+
+    fn reflect(&self, control: Option<Vec2>) -> Vec2 {
+        control.map_or(self.current, |control| self.current * 2.0 - control)
+    }
+
+    fn skip_separators(&mut self) {
+        while self.pos < self.data.len()
+            && (self.data[self.pos].is_ascii_whitespace() || self.data[self.pos] == b',')
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn point(&mut self) -> Result<Vec2, PathError> {
+        Ok(Vec2::new(self.number()?, self.number()?))
+    }
+
+    fn flag(&mut self) -> Result<bool, PathError> {
+        self.skip_separators();
+        let flag = match self.data.get(self.pos) {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return Err(PathError::ExpectedNumber(self.pos)),
+        };
+        self.pos += 1;
+        Ok(flag)
+    }
+
+    fn number(&mut self) -> Result<f32, PathError> {
+        self.skip_separators();
+        let start = self.pos;
+        let digits = |parser: &mut Self| {
+            let from = parser.pos;
+            while parser.data.get(parser.pos).is_some_and(u8::is_ascii_digit) {
+                parser.pos += 1;
+            }
+            parser.pos > from
+        };
+
+        if matches!(self.data.get(self.pos), Some(b'+' | b'-')) {
+            self.pos += 1;
+        }
+        let mut has_digits = digits(self);
+        if self.data.get(self.pos) == Some(&b'.') {
+            self.pos += 1;
+            has_digits |= digits(self);
+        }
+        if !has_digits {
+            self.pos = start;
+            return Err(PathError::ExpectedNumber(start));
+        }
+        if matches!(self.data.get(self.pos), Some(b'e' | b'E')) {
+            let mantissa_end = self.pos;
+            self.pos += 1;
+            if matches!(self.data.get(self.pos), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if !digits(self) {
+                self.pos = mantissa_end;
+            }
+        }
+
+        // the slice only holds ASCII digits, signs, dots and exponents
+        std::str::from_utf8(&self.data[start..self.pos])
+            .ok()
+            .and_then(|number| number.parse().ok())
+            .ok_or(PathError::ExpectedNumber(start))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn absolute_and_relative() {
+        let path = Path::parse("M10 20 L30,20 l0-10 H0 v5 Z").unwrap();
+        assert_eq!(
+            path.commands,
+            vec![
+                PathCommand::MoveTo(Vec2::new(10.0, 20.0)),
+                PathCommand::LineTo(Vec2::new(30.0, 20.0)),
+                PathCommand::LineTo(Vec2::new(30.0, 10.0)),
+                PathCommand::LineTo(Vec2::new(0.0, 10.0)),
+                PathCommand::LineTo(Vec2::new(0.0, 15.0)),
+                PathCommand::Close,
+            ]
+        );
+    }
+
+    #[test]
+    fn implicit_commands() {
+        // extra pairs after a move are lines, and compact numbers split on
+        // signs and second decimal points
+        let path = Path::parse("m1 1 2 0-1.5.5").unwrap();
+        assert_eq!(
+            path.commands,
+            vec![
+                PathCommand::MoveTo(Vec2::new(1.0, 1.0)),
+                PathCommand::LineTo(Vec2::new(3.0, 1.0)),
+                PathCommand::LineTo(Vec2::new(1.5, 1.5)),
+            ]
+        );
+    }
+
+    #[test]
+    fn smooth_curves() {
+        let path = Path::parse("M0 0 C0 10 10 10 10 0 S20 -10 20 0 Q25 5 30 0 T40 0").unwrap();
+        assert_eq!(
+            path.commands[2],
+            PathCommand::CubicTo(
+                Vec2::new(10.0, -10.0),
+                Vec2::new(20.0, -10.0),
+                Vec2::new(20.0, 0.0)
+            )
+        );
+        assert_eq!(
+            path.commands[4],
+            PathCommand::QuadTo(Vec2::new(35.0, -5.0), Vec2::new(40.0, 0.0))
+        );
+
+        // without a previous curve the control point is the current point
+        let path = Path::parse("M5 5 T10 10").unwrap();
+        assert_eq!(
+            path.commands[1],
+            PathCommand::QuadTo(Vec2::new(5.0, 5.0), Vec2::new(10.0, 10.0))
+        );
+    }
+
+    #[test]
+    fn arcs() {
+        let path = Path::parse("M0 0 a5 5 90 1010 0").unwrap();
+        assert_eq!(
+            path.commands[1],
+            PathCommand::ArcTo {
+                radii: Vec2::splat(5.0),
+                rotation: 90f32.to_radians(),
+                large_arc: true,
+                sweep: false,
+                end: Vec2::new(10.0, 0.0),
+            }
+        );
+    }
+
+    #[test]
+    fn close_returns_to_start() {
+        let path = Path::parse("M1 1 L5 1 Z l0 4").unwrap();
+        assert_eq!(path.commands[3], PathCommand::LineTo(Vec2::new(1.0, 5.0)));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Path::parse("L1 1"), Err(PathError::MissingMoveTo));
+        assert_eq!(Path::parse("1 1"), Err(PathError::MissingMoveTo));
+        assert_eq!(Path::parse("M1 1 L2"), Err(PathError::ExpectedNumber(7)));
+        assert_eq!(
+            Path::parse("M1 1 X2 2"),
+            Err(PathError::UnexpectedCharacter(5))
+        );
+        assert_eq!(
+            Path::parse("M0 0 A1 1 0 2 0 1 1"),
+            Err(PathError::ExpectedNumber(12))
+        );
+        assert_eq!(Path::parse(""), Ok(Path::new()));
+    }
+
+    #[test]
+    fn builder_matches_parser() {
+        let built = Path::new()
+            .move_to(Vec2::ZERO)
+            .line_to(Vec2::new(4.0, 0.0))
+            .quad_to(Vec2::new(4.0, 4.0), Vec2::new(0.0, 4.0))
+            .close();
+        assert_eq!(Ok(built), "M0 0 L4 0 Q4 4 0 4 Z".parse());
+    }
+}
@@ -0,0 +1,91 @@
+use glam::Vec2;
+
+use super::{Paint, Stroke};
+
+/// How a shape decides which areas are inside when its outline crosses
+/// itself or it has several contours.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum FillRule {
+    /// An area is inside if the outline winds around it at all.
+    #[default]
+    NonZero,
+    /// An area is inside if the outline crosses an odd number of times to
+    /// get out of it.
+    EvenOdd,
+}
+
+/// A closed shape with straight edges.
+///
+/// If neither a fill nor a stroke is set, the polygon is filled white.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Polygon {
+    /// The vertices in canvas coordinates.
+    pub points: Vec<Vec2>,
+    /// The rule used to fill self-intersecting outlines.
+    pub fill_rule: FillRule,
+    /// The fill color.
+    pub fill_color: Option<Paint>,
+    /// The outline.
+    pub stroke: Option<Stroke>,
+}
+
+impl Polygon {
+    pub fn new(points: impl Into<Vec<Vec2>>) -> Self {
+        Self {
+            points: points.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the fill, or `None` if the polygon is only stroked.
+    pub(crate) fn fill(&self) -> Option<Paint> {
+        match (&self.fill_color, &self.stroke) {
+            (None, Some(_)) => None,
+            (color, _) => Some(color.clone().unwrap_or_default()),
+        }
+    }
+}
+
+pub trait PolygonLike: Sized + AsMut<Polygon> + AsRef<Polygon> {
+    type Like: AsMut<Polygon> + AsRef<Polygon>;
+
+    fn like(self) -> Self::Like;
+
+    fn fill_rule(self, rule: FillRule) -> Self::Like {
+        let mut polygon = self.like();
+        polygon.as_mut().fill_rule = rule;
+        polygon
+    }
+
+    fn fill_color(self, color: impl Into<Paint>) -> Self::Like {
+        let mut polygon = self.like();
+        polygon.as_mut().fill_color = Some(color.into());
+        polygon
+    }
+
+    fn stroke(self, stroke: Stroke) -> Self::Like {
+        let mut polygon = self.like();
+        polygon.as_mut().stroke = Some(stroke);
+        polygon
+    }
+}
+
+impl AsRef<Polygon> for Polygon {
+    fn as_ref(&self) -> &Polygon {
+        self
+    }
+}
+
+impl AsMut<Polygon> for Polygon {
+    fn as_mut(&mut self) -> &mut Polygon {
+        self
+    }
+}
+
+impl PolygonLike for Polygon {
+    type Like = Polygon;
+
+    fn like(self) -> Self::Like {
+        self
+    }
+}
@@ -0,0 +1,87 @@
+use glam::Vec2;
+
+use super::Paint;
+
+/// An axis-aligned rectangle, optionally with rounded corners.
+///
+/// Rectangles are aligned to pixel edges rather than pixel centres, so whole
+/// number positions and sizes give crisp edges. The stroke is drawn inside
+/// the edges, so a stroked rectangle covers the same area as a filled one.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Rect {
+    pub size: Vec2,
+    pub corner_radius: f32,
+    pub stroke_width: Option<f32>,
+    pub stroke_color: Option<Paint>,
+}
+
+impl Rect {
+    pub fn with_size(size: Vec2) -> Self {
+        Self {
+            size,
+            corner_radius: 0.0,
+            stroke_width: None,
+            stroke_color: None,
+        }
+    }
+
+    pub fn square(side: f32) -> Self {
+        Self::with_size(Vec2::splat(side))
+    }
+}
+
+pub trait RectLike: Sized + AsMut<Rect> + AsRef<Rect> {
+    type Like: AsMut<Rect> + AsRef<Rect>;
+
+    fn like(self) -> Self::Like;
+
+    fn corner_radius(self, radius: f32) -> Self::Like {
+        let mut rect = self.like();
+        rect.as_mut().corner_radius = radius;
+        rect
+    }
+
+    fn stroke(self, stroke: f32) -> Self::Like {
+        let mut rect = self.like();
+        rect.as_mut().stroke_width = Some(stroke);
+        rect
+    }
+
+    fn stroke_color(self, color: impl Into<Paint>) -> Self::Like {
+        let mut rect = self.like();
+        rect.as_mut().stroke_color = Some(color.into());
+        rect
+    }
+
+    fn maybe_stroke_color(self, color: Option<impl Into<Paint>>) -> Self::Like {
+        let mut rect = self.like();
+        rect.as_mut().stroke_color = color.map(Into::into);
+        rect
+    }
+
+    fn solid(self) -> Self::Like {
+        let mut rect = self.like();
+        rect.as_mut().stroke_width = Some(rect.as_ref().size.min_element() / 2.0);
+        rect
+    }
+}
+
+impl AsRef<Rect> for Rect {
+    fn as_ref(&self) -> &Rect {
+        self
+    }
+}
+
+impl AsMut<Rect> for Rect {
+    fn as_mut(&mut self) -> &mut Rect {
+        self
+    }
+}
+
+impl RectLike for Rect {
+    type Like = Rect;
+
+    fn like(self) -> Self::Like {
+        self
+    }
+}
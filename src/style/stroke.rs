@@ -0,0 +1,195 @@
+use rgb::Rgb;
+
+/// How the open ends of a stroke are drawn.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum LineCap {
+    /// The stroke stops at the end point.
+    #[default]
+    Butt,
+    /// A half circle is drawn around the end point.
+    Round,
+    /// The stroke extends half its width past the end point.
+    Square,
+}
+
+/// How two segments of a stroke meet.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum LineJoin {
+    /// The outer edges are extended until they meet, up to the miter limit.
+    Miter,
+    /// The corner is rounded off.
+    Round,
+    /// The corner is cut off.
+    #[default]
+    Bevel,
+}
+
+/// A pattern of alternating dashes and gaps.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Dash {
+    /// Lengths of dashes and gaps in canvas pixels, starting with a dash.
+    ///
+    /// A pattern with an odd number of lengths is repeated to make it even.
+    pub pattern: Vec<f32>,
+    /// How far into the pattern the line starts. Changing this every frame
+    /// makes the dashes crawl along the line.
+    pub offset: f32,
+}
+
+impl Dash {
+    pub fn new(pattern: impl Into<Vec<f32>>) -> Self {
+        Self {
+            pattern: pattern.into(),
+            offset: 0.0,
+        }
+    }
+
+    /// Sets how far into the pattern the line starts, the same as
+    /// [`DashLike::offset`].
+    pub fn offset(self, offset: f32) -> Self {
+        DashLike::offset(self, offset)
+    }
+}
+
+pub trait DashLike: Sized + AsMut<Dash> + AsRef<Dash> {
+    type Like: AsMut<Dash> + AsRef<Dash>;
+
+    fn like(self) -> Self::Like;
+
+    fn pattern(self, pattern: impl Into<Vec<f32>>) -> Self::Like {
+        let mut dash = self.like();
+        dash.as_mut().pattern = pattern.into();
+        dash
+    }
+
+    fn offset(self, offset: f32) -> Self::Like {
+        let mut dash = self.like();
+        dash.as_mut().offset = offset;
+        dash
+    }
+}
+
+impl AsRef<Dash> for Dash {
+    fn as_ref(&self) -> &Dash {
+        self
+    }
+}
+
+impl AsMut<Dash> for Dash {
+    fn as_mut(&mut self) -> &mut Dash {
+        self
+    }
+}
+
+impl DashLike for Dash {
+    type Like = Dash;
+
+    fn like(self) -> Self::Like {
+        self
+    }
+}
+
+/// How the outline of a shape or a polyline is drawn.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stroke {
+    /// The width in canvas pixels.
+    pub width: f32,
+    /// The stroke color.
+    pub color: Option<Rgb<u8>>,
+    /// How open ends are drawn.
+    pub cap: LineCap,
+    /// How corners are drawn.
+    pub join: LineJoin,
+    /// The longest a miter may be, as a multiple of half the stroke width,
+    /// before the corner is bevelled instead.
+    pub miter_limit: f32,
+    /// The dash pattern, or a solid line if not set.
+    pub dash: Option<Dash>,
+}
+
+impl Default for Stroke {
+    fn default() -> Self {
+        Self::with_width(1.0)
+    }
+}
+
+impl Stroke {
+    pub fn with_width(width: f32) -> Self {
+        Self {
+            width,
+            color: None,
+            cap: LineCap::default(),
+            join: LineJoin::default(),
+            miter_limit: 4.0,
+            dash: None,
+        }
+    }
+}
+
+pub trait StrokeLike: Sized + AsMut<Stroke> + AsRef<Stroke> {
+    type Like: AsMut<Stroke> + AsRef<Stroke>;
+
+    fn like(self) -> Self::Like;
+
+    fn width(self, width: f32) -> Self::Like {
+        let mut stroke = self.like();
+        stroke.as_mut().width = width;
+        stroke
+    }
+
+    fn color(self, color: Rgb<u8>) -> Self::Like {
+        let mut stroke = self.like();
+        stroke.as_mut().color = Some(color);
+        stroke
+    }
+
+    fn cap(self, cap: LineCap) -> Self::Like {
+        let mut stroke = self.like();
+        stroke.as_mut().cap = cap;
+        stroke
+    }
+
+    fn join(self, join: LineJoin) -> Self::Like {
+        let mut stroke = self.like();
+        stroke.as_mut().join = join;
+        stroke
+    }
+
+    fn miter_limit(self, limit: f32) -> Self::Like {
+        let mut stroke = self.like();
+        stroke.as_mut().miter_limit = limit;
+        stroke
+    }
+
+    fn dash(self, dash: Dash) -> Self::Like {
+        let mut stroke = self.like();
+        stroke.as_mut().dash = Some(dash);
+        stroke
+    }
+
+    fn maybe_color(self, color: Option<Rgb<u8>>) -> Self::Like {
+        let mut stroke = self.like();
+        stroke.as_mut().color = color;
+        stroke
+    }
+}
+
+impl AsRef<Stroke> for Stroke {
+    fn as_ref(&self) -> &Stroke {
+        self
+    }
+}
+
+impl AsMut<Stroke> for Stroke {
+    fn as_mut(&mut self) -> &mut Stroke {
+        self
+    }
+}
+
+impl StrokeLike for Stroke {
+    type Like = Stroke;
+
+    fn like(self) -> Self::Like {
+        self
+    }
+}
@@ -0,0 +1,121 @@
+use rgb::Rgb;
+
+use crate::font::{HersheyFont, CAP_HEIGHT};
+
+/// Text drawn with a vector stroke font onto the pixel canvas.
+///
+/// Unlike [`StyledPrint`](super::StyledPrint), stroke text can be placed at
+/// any position, scaled to any size and rotated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrokeText<'a> {
+    /// The text to draw.
+    pub content: &'a str,
+    /// The cap height in canvas pixels.
+    pub height: f32,
+    /// The rotation around the start of the baseline, in radians.
+    pub rotation: f32,
+    /// The stroke color.
+    pub color: Option<Rgb<u8>>,
+    /// The font to draw with, or the built-in font if not set.
+    pub font: Option<&'a HersheyFont>,
+}
+
+impl<'a> StrokeText<'a> {
+    pub fn new(content: &'a str) -> Self {
+        Self {
+            content,
+            height: 10.0,
+            rotation: 0.0,
+            color: None,
+            font: None,
+        }
+    }
+
+    /// Returns the font the text is drawn with.
+    pub fn font(&self) -> &'a HersheyFont {
+        match self.font {
+            Some(font) => font,
+            None => HersheyFont::builtin(),
+        }
+    }
+
+    /// The scale from font units to canvas pixels.
+    pub fn scale(&self) -> f32 {
+        self.height / CAP_HEIGHT
+    }
+
+    /// The length of the baseline in canvas pixels.
+    pub fn width(&self) -> f32 {
+        self.font().text_width(self.content) * self.scale()
+    }
+}
+
+pub trait StrokeTextLike<'a>: Sized + AsMut<StrokeText<'a>> + AsRef<StrokeText<'a>> {
+    type Like: AsMut<StrokeText<'a>> + AsRef<StrokeText<'a>>;
+
+    fn like(self) -> Self::Like;
+
+    fn height(self, height: f32) -> Self::Like {
+        let mut text = self.like();
+        text.as_mut().height = height;
+        text
+    }
+
+    fn rotation(self, radians: f32) -> Self::Like {
+        let mut text = self.like();
+        text.as_mut().rotation = radians;
+        text
+    }
+
+    fn color(self, color: Rgb<u8>) -> Self::Like {
+        let mut text = self.like();
+        text.as_mut().color = Some(color);
+        text
+    }
+
+    fn with_font(self, font: &'a HersheyFont) -> Self::Like {
+        let mut text = self.like();
+        text.as_mut().font = Some(font);
+        text
+    }
+}
+
+impl<'a> AsRef<StrokeText<'a>> for StrokeText<'a> {
+    fn as_ref(&self) -> &StrokeText<'a> {
+        self
+    }
+}
+
+impl<'a> AsMut<StrokeText<'a>> for StrokeText<'a> {
+    fn as_mut(&mut self) -> &mut StrokeText<'a> {
+        self
+    }
+}
+
+impl<'a> StrokeTextLike<'a> for StrokeText<'a> {
+    type Like = StrokeText<'a>;
+
+    fn like(self) -> Self::Like {
+        self
+    }
+}
+
+impl<'a> From<&'a str> for StrokeText<'a> {
+    fn from(content: &'a str) -> Self {
+        Self::new(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn width_scales_with_height() {
+        let text = StrokeText::new("clod");
+        let double = text.height(text.height * 2.0);
+        assert!(text.width() > 0.0);
+        assert_eq!(double.width(), text.width() * 2.0);
+        assert_eq!(StrokeText::new("").width(), 0.0);
+    }
+}
@@ -0,0 +1,107 @@
+use glam::Vec2;
+use rgb::Rgb;
+
+/// A corner of a [`Triangle`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Vertex {
+    pub pos: Vec2,
+    pub color: Rgb<u8>,
+    /// The distance from the viewer, where smaller is closer. Only used
+    /// when depth testing.
+    pub depth: f32,
+}
+
+impl Vertex {
+    pub fn new(pos: Vec2, color: Rgb<u8>) -> Self {
+        Self {
+            pos,
+            color,
+            depth: 0.0,
+        }
+    }
+}
+
+/// A filled triangle with colors blended smoothly between its corners.
+///
+/// Pixels are filled if their centre is inside the triangle, with pixels on
+/// shared edges going to exactly one triangle, so meshes have no cracks or
+/// overlaps. Edges are not anti-aliased.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Triangle {
+    pub vertices: [Vertex; 3],
+    /// Whether pixels are only drawn if they are closer than what was
+    /// already drawn there, checked against the canvas depth buffer.
+    pub depth_test: bool,
+}
+
+impl Triangle {
+    /// Creates a white triangle.
+    pub fn new(points: [Vec2; 3]) -> Self {
+        Self {
+            vertices: points.map(|pos| Vertex::new(pos, Rgb::new(255, 255, 255))),
+            depth_test: false,
+        }
+    }
+
+    pub fn from_vertices(vertices: [Vertex; 3]) -> Self {
+        Self {
+            vertices,
+            depth_test: false,
+        }
+    }
+}
+
+pub trait TriangleLike: Sized + AsMut<Triangle> + AsRef<Triangle> {
+    type Like: AsMut<Triangle> + AsRef<Triangle>;
+
+    fn like(self) -> Self::Like;
+
+    /// Sets the color of every corner.
+    fn color(self, color: Rgb<u8>) -> Self::Like {
+        self.colors([color; 3])
+    }
+
+    fn colors(self, colors: [Rgb<u8>; 3]) -> Self::Like {
+        let mut triangle = self.like();
+        for (vertex, color) in triangle.as_mut().vertices.iter_mut().zip(colors) {
+            vertex.color = color;
+        }
+        triangle
+    }
+
+    /// Sets the depth of each corner and turns on depth testing.
+    fn depths(self, depths: [f32; 3]) -> Self::Like {
+        let mut triangle = self.like();
+        for (vertex, depth) in triangle.as_mut().vertices.iter_mut().zip(depths) {
+            vertex.depth = depth;
+        }
+        triangle.as_mut().depth_test = true;
+        triangle
+    }
+
+    fn depth_test(self, depth_test: bool) -> Self::Like {
+        let mut triangle = self.like();
+        triangle.as_mut().depth_test = depth_test;
+        triangle
+    }
+}
+
+impl AsRef<Triangle> for Triangle {
+    fn as_ref(&self) -> &Triangle {
+        self
+    }
+}
+
+impl AsMut<Triangle> for Triangle {
+    fn as_mut(&mut self) -> &mut Triangle {
+        self
+    }
+}
+
+impl TriangleLike for Triangle {
+    type Like = Triangle;
+
+    fn like(self) -> Self::Like {
+        self
+    }
+}
@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+/// Splits the time between frames into fixed ticks, carrying over what is
+/// left to the next frame.
+#[derive(Debug, Clone)]
+pub(crate) struct FixedTimestep {
+    tick: Duration,
+    max_ticks: u32,
+    accumulator: Duration,
+}
+
+impl FixedTimestep {
+    pub(crate) fn new(tick: Duration, max_ticks: u32) -> Self {
+        Self {
+            tick,
+            max_ticks,
+            accumulator: Duration::ZERO,
+        }
+    }
+
+    /// Adds the time since the last frame and returns how many ticks to run.
+    pub(crate) fn advance(&mut self, elapsed: Duration) -> u32 {
+        if self.tick.is_zero() {
+            return 0;
+        }
+        self.accumulator += elapsed;
+        let ticks = self.accumulator.as_nanos() / self.tick.as_nanos();
+        if ticks > self.max_ticks as u128 {
+            // drop what cannot be caught up on instead of falling further
+            // behind every frame
+            self.accumulator =
+                Duration::from_nanos((self.accumulator.as_nanos() % self.tick.as_nanos()) as u64);
+            return self.max_ticks;
+        }
+        let ticks = ticks as u32;
+        self.accumulator -= self.tick * ticks;
+        ticks
+    }
+
+    /// Drops the time left over, so the next frame starts a fresh tick.
+    pub(crate) fn reset(&mut self) {
+        self.accumulator = Duration::ZERO;
+    }
+
+    /// Returns how far the time left over is into the next tick, from `0.0`
+    /// to `1.0`.
+    pub(crate) fn alpha(&self) -> f32 {
+        if self.tick.is_zero() {
+            return 0.0;
+        }
+        self.accumulator.as_secs_f32() / self.tick.as_secs_f32()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advance() {
+        let mut timestep = FixedTimestep::new(Duration::from_millis(10), 4);
+        assert_eq!(timestep.advance(Duration::from_millis(25)), 2);
+        assert_eq!(timestep.alpha(), 0.5);
+        assert_eq!(timestep.advance(Duration::from_millis(5)), 1);
+        assert_eq!(timestep.alpha(), 0.0);
+        assert_eq!(timestep.advance(Duration::from_millis(3)), 0);
+    }
+
+    #[test]
+    fn max_ticks() {
+        let mut timestep = FixedTimestep::new(Duration::from_millis(10), 4);
+        assert_eq!(timestep.advance(Duration::from_millis(1003)), 4);
+        assert!((timestep.alpha() - 0.3).abs() < 1e-6);
+        assert_eq!(timestep.advance(Duration::from_millis(7)), 1);
+
+        // more ticks than fit in a u32 are still capped
+        let mut timestep = FixedTimestep::new(Duration::from_nanos(1), 4);
+        assert_eq!(
+            timestep.advance(Duration::from_nanos(u32::MAX as u64 + 2)),
+            4
+        );
+        assert_eq!(timestep.alpha(), 0.0);
+    }
+
+    #[test]
+    fn reset() {
+        let mut timestep = FixedTimestep::new(Duration::from_millis(10), 4);
+        assert_eq!(timestep.advance(Duration::from_millis(8)), 0);
+        timestep.reset();
+        assert_eq!(timestep.alpha(), 0.0);
+        assert_eq!(timestep.advance(Duration::from_millis(8)), 0);
+    }
+}